@@ -0,0 +1,124 @@
+//! Module to measure the peak memory allocated while solving puzzles
+//!
+//! The measurement requires [`PeakAlloc`] to be installed as global allocator in the binary:
+//!
+//! ```
+//! use mr_kaffee_aoc::alloc::PeakAlloc;
+//!
+//! #[global_allocator]
+//! static ALLOC: PeakAlloc = PeakAlloc;
+//! ```
+//!
+//! [`crate::Puzzle::solve_timed`] then reports the peak memory in its summary line.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+/// Global allocator delegating to [`System`] which keeps track of the bytes allocated
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PeakAlloc;
+
+fn add(size: usize) {
+    INSTALLED.store(true, Ordering::Relaxed);
+    let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+fn sub(size: usize) {
+    CURRENT.fetch_sub(size, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for PeakAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            add(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            add(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        sub(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            if new_size > layout.size() {
+                add(new_size - layout.size());
+            } else {
+                sub(layout.size() - new_size);
+            }
+        }
+        new_ptr
+    }
+}
+
+/// reset the peak to the bytes currently allocated and return them
+pub fn reset_peak() -> usize {
+    let current = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(current, Ordering::Relaxed);
+    current
+}
+
+/// get the peak bytes allocated since the last [`reset_peak`], `None` if [`PeakAlloc`] is not
+/// the global allocator
+pub fn peak() -> Option<usize> {
+    INSTALLED
+        .load(Ordering::Relaxed)
+        .then(|| PEAK.load(Ordering::Relaxed))
+}
+
+/// format a number of bytes with a binary unit, e.g., `1.5 KiB`
+///
+/// # Examples
+/// ```
+/// # use mr_kaffee_aoc::alloc::format_bytes;
+/// assert_eq!("512 B", format_bytes(512));
+/// assert_eq!("1.5 KiB", format_bytes(1536));
+/// assert_eq!("2.0 MiB", format_bytes(2 << 20));
+/// ```
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOC: PeakAlloc = PeakAlloc;
+
+    #[test]
+    pub fn test_peak() {
+        let baseline = reset_peak();
+        let data = std::hint::black_box(vec![1u8; 16 << 20]);
+        drop(data);
+        // other tests allocate concurrently, so only check a lower bound
+        assert!(peak().unwrap().saturating_sub(baseline) >= 8 << 20);
+    }
+}
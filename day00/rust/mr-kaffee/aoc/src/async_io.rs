@@ -0,0 +1,126 @@
+use crate::{
+    err::PuzzleError,
+    puzzle_io::{check_network, validate, PuzzleIO},
+};
+use futures::future::join_all;
+use std::future::Future;
+
+/// Asynchronous variant of [`crate::template::InputProvider`]
+pub trait AsyncInputProvider {
+    fn load_input(&self, year: u16, day: u16) -> impl Future<Output = Result<String, PuzzleError>>;
+}
+
+/// Asynchronous puzzle IO
+///
+/// Uses the session, timeout, and user agent of the wrapped [`PuzzleIO`]. Requests are not
+/// retried.
+///
+/// The requests are sent with the asynchronous client of `reqwest`, so the futures must be
+/// run within a [tokio](https://tokio.rs) runtime. Other executors, e.g.,
+/// `futures::executor::block_on`, are not supported.
+#[derive(Debug)]
+pub struct AsyncPuzzleIO {
+    io: PuzzleIO,
+    client: reqwest::Client,
+}
+
+impl TryFrom<PuzzleIO> for AsyncPuzzleIO {
+    type Error = PuzzleError;
+
+    fn try_from(io: PuzzleIO) -> Result<Self, Self::Error> {
+        let mut builder = reqwest::Client::builder().timeout(io.timeout);
+        if let Some(proxy) = &io.proxy {
+            builder = builder.proxy(proxy.clone());
+        }
+        let client = builder
+            .build()
+            .map_err(|err| PuzzleError::from(err.to_string()))?;
+        Ok(Self { io, client })
+    }
+}
+
+impl AsyncInputProvider for AsyncPuzzleIO {
+    async fn load_input(&self, year: u16, day: u16) -> Result<String, PuzzleError> {
+        check_network()?;
+        validate(year, day)?;
+
+        let response = self
+            .client
+            .get(self.io.url(year, day, "input")?)
+            .header("Cookie", format!("session={}", self.io.session))
+            .header("User-Agent", &self.io.user_agent)
+            .send()
+            .await
+            .map_err(|err| PuzzleIO::map_send_err(err, year, day))?;
+
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|err| PuzzleIO::map_send_err(err, year, day))?;
+
+        if !status.is_success() {
+            return Err(PuzzleIO::status_err(year, day, status, &text));
+        }
+
+        Ok(text)
+    }
+}
+
+/// Fetch inputs for all 25 days of a `year` concurrently
+///
+/// The results are returned in order of days, i.e., the first element is the result for day 1.
+pub async fn fetch_all_days<P: AsyncInputProvider>(
+    year: u16,
+    provider: &P,
+) -> Vec<Result<String, PuzzleError>> {
+    join_all((1..=25).map(|day| provider.load_input(year, day))).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+
+    struct TestInputProvider {}
+
+    impl AsyncInputProvider for TestInputProvider {
+        async fn load_input(&self, year: u16, day: u16) -> Result<String, PuzzleError> {
+            if day == 1 {
+                Err(PuzzleError::from("No input for day 1"))
+            } else {
+                Ok(format!("Test input for {}/{}\n", year, day))
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_fetch_all_days() {
+        let results = block_on(fetch_all_days(2022, &TestInputProvider {}));
+        assert_eq!(25, results.len());
+        assert!(results[0].is_err());
+        assert_eq!("Test input for 2022/2\n", results[1].as_ref().unwrap());
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-network"))]
+    pub fn test_async_puzzle_io_load_input() {
+        let (base_url, server) = crate::puzzle_io::tests::serve_once(b"async input\n");
+        let io = PuzzleIO::from("my-session").with_base_url(&base_url);
+        let io = AsyncPuzzleIO::try_from(io).unwrap();
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let input = runtime.block_on(io.load_input(2022, 1)).unwrap();
+        assert_eq!("async input\n", input);
+
+        let request = server.join().unwrap();
+        assert_eq!("GET /2022/day/1/input HTTP/1.1", request[0]);
+        assert!(
+            request.contains(&"cookie: session=my-session".to_string()),
+            "{request:?}"
+        );
+    }
+}
@@ -0,0 +1,270 @@
+//! Module to compare the time it takes to solve stars against a baseline saved in a JSON file
+//!
+//! This is a lightweight alternative to criterion benchmarks to spot regressions across runs.
+//! The first run records the durations, later runs flag every star which got slower than the
+//! recorded duration by more than a threshold.
+//!
+//! # Examples
+//! ```
+//! # use mr_kaffee_aoc::{baseline::Baseline, Puzzle, Star};
+//! # let path = std::env::temp_dir().join("mr-kaffee-aoc-baseline-doc.json");
+//! # let _ = std::fs::remove_file(&path);
+//! let puzzle: Puzzle<'_, &str, usize, usize, usize, usize> = Puzzle {
+//!     year: 2022,
+//!     day: 24,
+//!     input: "hello",
+//!     star1: Some(Star { name: "length", f: &(|v: &&str| v.len()), exp: Some(5) }),
+//!     star2: None,
+//! };
+//! let baseline = Baseline::new(&path).with_threshold(50.0);
+//! let (_, regressions) = puzzle.solve_timed_baseline(&baseline).unwrap();
+//! assert!(regressions.is_empty()); // no baseline yet
+//! assert!(path.exists());
+//! # let _ = std::fs::remove_file(&path);
+//! ```
+use crate::{
+    err::PuzzleError,
+    summary::{RunSummary, Status},
+    SolveReport,
+};
+use serde_json::{Map, Value};
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// default path of the baseline file
+pub const DEFAULT_BASELINE_PATH: &str = "target/aoc-baseline.json";
+
+/// default threshold in percent a star may get slower before it is flagged
+pub const DEFAULT_THRESHOLD: f64 = 20.0;
+
+/// Baseline of durations to solve stars stored in a JSON file
+///
+/// The file contains an object with keys `"{year}/{day}/star{star}"`, e.g., `"2022/01/star1"`,
+/// and durations in nanoseconds as values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Baseline {
+    /// path to the JSON file
+    pub path: PathBuf,
+    /// threshold in percent a star may get slower before it is flagged
+    pub threshold: f64,
+    /// replace recorded durations with the durations of the current run
+    pub update: bool,
+}
+
+impl Default for Baseline {
+    fn default() -> Self {
+        Self::new(DEFAULT_BASELINE_PATH)
+    }
+}
+
+impl Baseline {
+    /// Create a baseline stored at `path` with the [`DEFAULT_THRESHOLD`]
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            threshold: DEFAULT_THRESHOLD,
+            update: false,
+        }
+    }
+
+    /// Set the threshold in percent
+    pub fn with_threshold(self, threshold: f64) -> Self {
+        Self { threshold, ..self }
+    }
+
+    /// Set the flag to replace recorded durations
+    pub fn with_update(self, update: bool) -> Self {
+        Self { update, ..self }
+    }
+
+    /// Compare the stars of a [`SolveReport`] against the baseline, see [`Baseline::check`]
+    pub fn check_report<T1, T2>(
+        &self,
+        report: &SolveReport<T1, T2>,
+    ) -> Result<Vec<Regression>, PuzzleError> {
+        let timings = [
+            (1, report.star1.as_ref().map(|star| star.duration)),
+            (2, report.star2.as_ref().map(|star| star.duration)),
+        ];
+        self.check(
+            timings
+                .into_iter()
+                .filter_map(|(star, duration)| Some((report.year, report.day, star, duration?))),
+        )
+    }
+
+    /// Compare the rows of a [`RunSummary`] against the baseline, rows with errors are skipped,
+    /// see [`Baseline::check`]
+    pub fn check_summary(&self, summary: &RunSummary) -> Result<Vec<Regression>, PuzzleError> {
+        self.check(
+            summary
+                .rows
+                .iter()
+                .filter(|row| row.status != Status::Error)
+                .map(|row| (row.year, row.day, row.star, row.duration)),
+        )
+    }
+
+    /// Compare `(year, day, star, duration)` timings against the baseline and return the stars
+    /// which got slower by more than the threshold
+    ///
+    /// Stars not yet in the baseline are recorded. Recorded durations are only replaced if
+    /// `update` is set. A missing baseline file is created.
+    pub fn check<I>(&self, timings: I) -> Result<Vec<Regression>, PuzzleError>
+    where
+        I: IntoIterator<Item = (u16, u16, u8, Duration)>,
+    {
+        let mut recorded = self.load()?;
+        let mut regressions = Vec::new();
+        let mut changed = false;
+        for (year, day, star, duration) in timings {
+            let key = format!("{year}/{day:02}/star{star}");
+            let nanos = duration.as_nanos() as u64;
+            match recorded.get(&key).and_then(Value::as_u64) {
+                Some(baseline) => {
+                    let baseline = Duration::from_nanos(baseline);
+                    let regression = Regression {
+                        year,
+                        day,
+                        star,
+                        baseline,
+                        duration,
+                    };
+                    if regression.slowdown() > self.threshold {
+                        regressions.push(regression);
+                    }
+                    if self.update {
+                        recorded.insert(key, nanos.into());
+                        changed = true;
+                    }
+                }
+                None => {
+                    recorded.insert(key, nanos.into());
+                    changed = true;
+                }
+            }
+        }
+
+        if changed {
+            self.save(&recorded)?;
+        }
+
+        Ok(regressions)
+    }
+
+    fn load(&self) -> Result<Map<String, Value>, PuzzleError> {
+        let context = || format!("Failed to read baseline {}", self.path.to_string_lossy());
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Map::new()),
+            Err(err) => return Err(PuzzleError::from(err).with_context(context())),
+        };
+        serde_json::from_str(&content)
+            .map_err(|err| PuzzleError::from(format!("{}: {err}", context())))
+    }
+
+    fn save(&self, recorded: &Map<String, Value>) -> Result<(), PuzzleError> {
+        let context = || format!("Failed to write baseline {}", self.path.to_string_lossy());
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .map_err(|err| PuzzleError::from(err).with_context(context()))?;
+        }
+        let content = serde_json::to_string_pretty(recorded)
+            .map_err(|err| PuzzleError::from(format!("{}: {err}", context())))?;
+        fs::write(&self.path, content + "\n")
+            .map_err(|err| PuzzleError::from(err).with_context(context()))
+    }
+}
+
+/// Star which got slower than its baseline, see [`Baseline::check`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regression {
+    /// year of the puzzle
+    pub year: u16,
+    /// day of the puzzle
+    pub day: u16,
+    /// the star, `1` or `2`
+    pub star: u8,
+    /// the recorded duration
+    pub baseline: Duration,
+    /// the duration of the current run
+    pub duration: Duration,
+}
+
+impl Regression {
+    /// how much slower the current run is in percent, negative if it is faster
+    pub fn slowdown(&self) -> f64 {
+        let baseline = self.baseline.as_secs_f64().max(1e-9);
+        (self.duration.as_secs_f64() / baseline - 1.0) * 100.0
+    }
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Star {} of {:04}/{:02} took {:?}, {:.1}% slower than the baseline {:?}",
+            self.star,
+            self.year,
+            self.day,
+            self.duration,
+            self.slowdown(),
+            self.baseline
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_check() {
+        let path = Path::new("target/test_baseline_check/baseline.json");
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+        let ms = Duration::from_millis;
+        let baseline = Baseline::new(path).with_threshold(10.0);
+
+        // a missing baseline is recorded
+        let regressions = baseline
+            .check([(2022, 1, 1, ms(10)), (2022, 1, 2, ms(20))])
+            .unwrap();
+        assert!(regressions.is_empty());
+        let content = fs::read_to_string(path).unwrap();
+        assert!(
+            content.contains(r#""2022/01/star1": 10000000"#),
+            "{content}"
+        );
+
+        // only stars slower by more than the threshold are flagged, a new star is recorded
+        let regressions = baseline
+            .check([
+                (2022, 1, 1, ms(12)),
+                (2022, 1, 2, ms(21)),
+                (2022, 2, 1, ms(5)),
+            ])
+            .unwrap();
+        assert_eq!(1, regressions.len());
+        assert_eq!((1, ms(10), ms(12)), {
+            let r = &regressions[0];
+            (r.star, r.baseline, r.duration)
+        });
+        assert!((regressions[0].slowdown() - 20.0).abs() < 1e-6);
+        assert!(regressions[0].to_string().contains("20.0% slower"));
+
+        // recorded durations are kept unless updating
+        assert_eq!(1, baseline.check([(2022, 1, 1, ms(12))]).unwrap().len());
+        let baseline = baseline.with_update(true);
+        assert_eq!(1, baseline.check([(2022, 1, 1, ms(12))]).unwrap().len());
+        assert!(baseline.check([(2022, 1, 1, ms(12))]).unwrap().is_empty());
+
+        fs::write(path, "not json").unwrap();
+        let err = baseline.check([(2022, 1, 1, ms(12))]).unwrap_err();
+        assert!(err.to_string().contains("Failed to read baseline"), "{err}");
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+}
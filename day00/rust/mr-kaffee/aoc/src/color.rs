@@ -0,0 +1,55 @@
+//! Module to color terminal output with ANSI escape codes
+//!
+//! Colors are only used if standard out is a terminal and the `NO_COLOR` environment variable is
+//! not set to a non-empty value, see [no-color.org](https://no-color.org). Otherwise the text is
+//! returned unchanged.
+use std::{
+    fmt::Display,
+    io::{stdout, IsTerminal},
+    sync::OnceLock,
+};
+
+/// Colors supported by [`paint`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+}
+
+impl Color {
+    fn code(&self) -> &'static str {
+        match self {
+            Color::Red => "31",
+            Color::Green => "32",
+        }
+    }
+}
+
+/// check whether colors are enabled, the result is determined once and cached
+pub fn enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| {
+        std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty()) && stdout().is_terminal()
+    })
+}
+
+/// format `text` in the given color if colors are [`enabled`]
+pub fn paint<T: Display>(text: T, color: Color) -> String {
+    paint_if(text, color, enabled())
+}
+
+/// format `text` in the given color if `enabled` is set
+///
+/// # Examples
+/// ```
+/// # use mr_kaffee_aoc::color::{paint_if, Color};
+/// assert_eq!("\x1b[32mok\x1b[0m", paint_if("ok", Color::Green, true));
+/// assert_eq!("ok", paint_if("ok", Color::Green, false));
+/// ```
+pub fn paint_if<T: Display>(text: T, color: Color, enabled: bool) -> String {
+    if enabled {
+        format!("\x1b[{}m{}\x1b[0m", color.code(), text)
+    } else {
+        text.to_string()
+    }
+}
@@ -0,0 +1,245 @@
+//! Module to read user settings from a configuration file
+//!
+//! The file is a TOML file, by default located at `~/.config/aoc/config.toml`, with the
+//! optional keys `session`, `default_year`, `template_dir`, and `cache_dir`:
+//!
+//! ```toml
+//! session = "53616c7465645f5f..."
+//! default_year = 2022
+//! template_dir = "/home/me/aoc/templates"
+//! cache_dir = "/home/me/.cache/aoc"
+//! ```
+use crate::{err::PuzzleError, puzzle_io::PuzzleIO};
+use std::{
+    env,
+    ffi::OsString,
+    fs,
+    path::{Path, PathBuf},
+};
+use toml_edit::{DocumentMut, Item};
+
+/// Settings read from a configuration file, every setting is optional
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigFile {
+    /// the session cookie
+    pub session: Option<String>,
+    /// the year used if no year is given explicitly
+    pub default_year: Option<u16>,
+    /// directory with templates overriding the built-in ones
+    pub template_dir: Option<PathBuf>,
+    /// directory to cache inputs in
+    pub cache_dir: Option<PathBuf>,
+}
+
+impl ConfigFile {
+    /// get the default location of the configuration file, `$XDG_CONFIG_HOME/aoc/config.toml`
+    /// if `XDG_CONFIG_HOME` is set, `~/.config/aoc/config.toml` otherwise
+    pub fn default_path() -> Option<PathBuf> {
+        env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                env::var_os("HOME")
+                    .or_else(|| env::var_os("USERPROFILE"))
+                    .map(|home| PathBuf::from(home).join(".config"))
+            })
+            .map(|dir| dir.join("aoc").join("config.toml"))
+    }
+
+    /// load the configuration file from its [default location](ConfigFile::default_path)
+    ///
+    /// If there is no such file, the default settings are returned.
+    pub fn load() -> Result<Self, PuzzleError> {
+        match Self::default_path() {
+            Some(path) => Self::from_file(path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// load the configuration file at `path`
+    ///
+    /// If there is no such file, the default settings are returned.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, PuzzleError> {
+        let path = path.as_ref();
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+
+        Self::parse(&fs::read_to_string(path)?)
+            .map_err(|err| err.with_context(path.to_string_lossy()))
+    }
+
+    /// parse the `content` of a configuration file
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_aoc::config::ConfigFile;
+    /// let config = ConfigFile::parse("session = \"abc\"\ndefault_year = 2022").unwrap();
+    /// assert_eq!(Some("abc".to_string()), config.session);
+    /// assert_eq!(Some(2022), config.default_year);
+    /// assert_eq!(None, config.cache_dir);
+    ///
+    /// assert!(ConfigFile::parse("default_year = \"last year\"").is_err());
+    /// ```
+    pub fn parse(content: &str) -> Result<Self, PuzzleError> {
+        let doc = content
+            .parse::<DocumentMut>()
+            .map_err(|err| PuzzleError::from(err.to_string()))?;
+
+        let string = |key: &str| -> Result<Option<String>, PuzzleError> {
+            match doc.get(key) {
+                None => Ok(None),
+                Some(item) => item
+                    .as_str()
+                    .map(|value| Some(value.to_string()))
+                    .ok_or_else(|| PuzzleError::from(format!("'{key}' is not a string"))),
+            }
+        };
+
+        let default_year = match doc.get("default_year").and_then(Item::as_integer) {
+            Some(year) => Some(
+                u16::try_from(year)
+                    .map_err(|_| PuzzleError::from(format!("Invalid default_year {year}")))?,
+            ),
+            None if doc.contains_key("default_year") => {
+                return Err("'default_year' is not an integer".into())
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            session: string("session")?,
+            default_year,
+            template_dir: string("template_dir")?.map(PathBuf::from),
+            cache_dir: string("cache_dir")?.map(PathBuf::from),
+        })
+    }
+
+    /// get the directory to cache inputs in, `cache_dir` if set, the
+    /// [platform's default](ConfigFile::default_cache_dir) otherwise
+    pub fn resolve_cache_dir(&self) -> Result<PathBuf, PuzzleError> {
+        match &self.cache_dir {
+            Some(cache_dir) => Ok(cache_dir.clone()),
+            None => Self::default_cache_dir(),
+        }
+    }
+
+    /// get the platform's default directory to cache inputs in
+    ///
+    /// This is `%LOCALAPPDATA%\aoc` on Windows, `~/Library/Caches/aoc` on macOS, and
+    /// `$XDG_CACHE_HOME/aoc` or `~/.cache/aoc` elsewhere. The directory is not created here, a
+    /// [`CachingInputProvider`](crate::template::CachingInputProvider) creates it when storing
+    /// the first input.
+    ///
+    /// Returns an error of kind [`std::io::ErrorKind::NotFound`] if there is no home directory.
+    pub fn default_cache_dir() -> Result<PathBuf, PuzzleError> {
+        platform_cache_dir(env::consts::OS, |key| env::var_os(key)).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "No home directory to cache inputs in, set cache_dir in the configuration file",
+            )
+            .into()
+        })
+    }
+
+    /// create a [`PuzzleIO`] from the session, if any
+    pub fn puzzle_io(&self) -> Option<PuzzleIO> {
+        self.session
+            .as_deref()
+            .map(str::trim)
+            .filter(|session| !session.is_empty())
+            .map(PuzzleIO::from)
+    }
+}
+
+/// get the cache directory for the operating system `os` with environment variables looked up
+/// by `var`, see [`ConfigFile::default_cache_dir`]
+fn platform_cache_dir<F>(os: &str, var: F) -> Option<PathBuf>
+where
+    F: Fn(&str) -> Option<OsString>,
+{
+    let dir = |key: &str| var(key).filter(|dir| !dir.is_empty()).map(PathBuf::from);
+    let base = match os {
+        "windows" => dir("LOCALAPPDATA"),
+        "macos" => dir("HOME").map(|home| home.join("Library").join("Caches")),
+        _ => dir("XDG_CACHE_HOME").or_else(|| dir("HOME").map(|home| home.join(".cache"))),
+    };
+    base.map(|base| base.join("aoc"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn test_from_file() {
+        let root = Path::new("target/test_config_from_file");
+        let _ = fs::remove_dir_all(root);
+
+        // missing file
+        assert_eq!(
+            ConfigFile::default(),
+            ConfigFile::from_file(root.join("config.toml")).unwrap()
+        );
+
+        fs::create_dir_all(root).unwrap();
+        fs::write(
+            root.join("config.toml"),
+            "# my settings\nsession = \"abc\"\ntemplate_dir = \"templates\"\ncache_dir = \"cache\"\n",
+        )
+        .unwrap();
+        let config = ConfigFile::from_file(root.join("config.toml")).unwrap();
+        assert_eq!(
+            ConfigFile {
+                session: Some("abc".to_string()),
+                default_year: None,
+                template_dir: Some(PathBuf::from("templates")),
+                cache_dir: Some(PathBuf::from("cache")),
+            },
+            config
+        );
+        assert_eq!("abc", config.puzzle_io().unwrap().session);
+
+        fs::write(root.join("config.toml"), "session = 42").unwrap();
+        let err = ConfigFile::from_file(root.join("config.toml")).unwrap_err();
+        assert!(err.to_string().contains("'session' is not a string"));
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    pub fn test_platform_cache_dir() {
+        let vars = |vars: &'static [(&'static str, &'static str)]| {
+            move |key: &str| {
+                vars.iter()
+                    .find(|(k, _)| *k == key)
+                    .map(|(_, v)| OsString::from(v))
+            }
+        };
+        let home = vars(&[("HOME", "/home/me"), ("LOCALAPPDATA", "C:\\Local")]);
+        assert_eq!(
+            Some(Path::new("/home/me/.cache/aoc").to_path_buf()),
+            platform_cache_dir("linux", home)
+        );
+        assert_eq!(
+            Some(Path::new("/home/me/Library/Caches/aoc").to_path_buf()),
+            platform_cache_dir("macos", home)
+        );
+        assert_eq!(
+            Some(Path::new("C:\\Local").join("aoc")),
+            platform_cache_dir("windows", home)
+        );
+        let xdg = vars(&[("HOME", "/home/me"), ("XDG_CACHE_HOME", "/tmp/xdg")]);
+        assert_eq!(
+            Some(Path::new("/tmp/xdg/aoc").to_path_buf()),
+            platform_cache_dir("linux", xdg)
+        );
+        assert_eq!(None, platform_cache_dir("linux", vars(&[("HOME", "")])));
+
+        let config = ConfigFile {
+            cache_dir: Some(PathBuf::from(".cache")),
+            ..ConfigFile::default()
+        };
+        assert_eq!(Path::new(".cache"), config.resolve_cache_dir().unwrap());
+    }
+}
@@ -0,0 +1,236 @@
+//! Module for grid-style puzzle inputs
+//!
+//! Many AoC puzzles come with a 2D grid of characters. [`Grid`] stores the cells row by row and
+//! takes care of indexing and neighbors, so that solutions can focus on the puzzle.
+use crate::err::PuzzleError;
+use std::{fmt, ops::Index, str::FromStr};
+
+/// Rectangular grid of cells stored row by row
+///
+/// Cells are addressed by column `x` and row `y`, starting with `(0, 0)` in the top left
+/// corner.
+///
+/// # Examples
+/// ```
+/// # use mr_kaffee_aoc::grid::Grid;
+/// let grid = "#..\n.#.".parse::<Grid<char>>().unwrap();
+/// assert_eq!((3, 2), (grid.width(), grid.height()));
+/// assert_eq!(Some(&'#'), grid.get(1, 1));
+/// assert_eq!(None, grid.get(3, 0));
+/// assert_eq!(vec![(0, 1), (2, 1), (1, 0)], grid.neighbors4(1, 1).collect::<Vec<_>>());
+/// assert_eq!("#..\n.#.", grid.to_string());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+}
+
+/// offsets to the neighbors sharing an edge, see [`Grid::neighbors4`]
+const D4: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// offsets to the neighbors sharing an edge or a corner, see [`Grid::neighbors8`]
+const D8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+impl<T> Grid<T> {
+    /// create a grid from `cells` stored row by row with rows of length `width`
+    ///
+    /// # Panics
+    /// if `width` is zero or the number of cells is not a multiple of `width`
+    pub fn new(cells: Vec<T>, width: usize) -> Self {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "{} cells do not fit in rows of width {width}",
+            cells.len()
+        );
+        Self { cells, width }
+    }
+
+    /// number of columns
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// number of rows
+    pub fn height(&self) -> usize {
+        self.cells.len() / self.width
+    }
+
+    /// the cell in column `x` and row `y`, `None` if outside of the grid
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width {
+            self.cells.get(x + y * self.width)
+        } else {
+            None
+        }
+    }
+
+    /// the mutable cell in column `x` and row `y`, `None` if outside of the grid
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width {
+            self.cells.get_mut(x + y * self.width)
+        } else {
+            None
+        }
+    }
+
+    /// the cells of row `y`
+    ///
+    /// # Panics
+    /// if `y` is outside of the grid
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// iterate over all cells with their coordinates `((x, y), cell)` row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(k, cell)| ((k % width, k / width), cell))
+    }
+
+    /// the coordinates of the up to four cells sharing an edge with `(x, y)`, left, right,
+    /// above, and below
+    pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors(x, y, &D4)
+    }
+
+    /// the coordinates of the up to eight cells sharing an edge or a corner with `(x, y)`, row
+    /// by row
+    pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors(x, y, &D8)
+    }
+
+    fn neighbors<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        deltas: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let (width, height) = (self.width(), self.height());
+        deltas.iter().filter_map(move |&(dx, dy)| {
+            let x = x.checked_add_signed(dx).filter(|&x| x < width)?;
+            let y = y.checked_add_signed(dy).filter(|&y| y < height)?;
+            Some((x, y))
+        })
+    }
+
+    /// create a grid of the same size with `f` applied to every cell
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    /// # Panics
+    /// if `(x, y)` is outside of the grid
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        self.get(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) is outside of the grid"))
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = PuzzleError;
+
+    /// parse a grid from lines of characters, trailing empty lines are ignored
+    ///
+    /// It is an error if the input is empty or lines differ in length.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.trim_end_matches(['\r', '\n']).lines();
+        let mut cells = lines.next().unwrap_or_default().chars().collect::<Vec<_>>();
+        let width = cells.len();
+        if width == 0 {
+            return Err(PuzzleError::from("The grid is empty"));
+        }
+        for (y, line) in lines.enumerate() {
+            let len = cells.len();
+            cells.extend(line.chars());
+            if cells.len() - len != width {
+                return Err(PuzzleError::from(format!(
+                    "Row {} has {} columns, expected {width}",
+                    y + 1,
+                    cells.len() - len
+                )));
+            }
+        }
+        Ok(Self { cells, width })
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    /// rows separated by new lines, without a trailing new line
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in self.row(y) {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = "abc\ndef\nghi\n";
+
+    #[test]
+    pub fn test_from_str() {
+        let grid = CONTENT.parse::<Grid<char>>().unwrap();
+        assert_eq!((3, 3), (grid.width(), grid.height()));
+        assert_eq!('f', grid[(2, 1)]);
+        assert_eq!(&['g', 'h', 'i'], grid.row(2));
+        assert_eq!(None, grid.get(0, 3));
+
+        assert!("".parse::<Grid<char>>().is_err());
+        let err = "ab\nc".parse::<Grid<char>>().unwrap_err();
+        assert!(err.to_string().contains("Row 1 has 1 columns"), "{err}");
+    }
+
+    #[test]
+    pub fn test_neighbors() {
+        let grid = CONTENT.parse::<Grid<char>>().unwrap();
+        let cells = |n: &mut dyn Iterator<Item = (usize, usize)>| -> String {
+            n.map(|(x, y)| grid[(x, y)]).collect()
+        };
+        assert_eq!("dfbh", cells(&mut grid.neighbors4(1, 1)));
+        assert_eq!("abcdfghi", cells(&mut grid.neighbors8(1, 1)));
+        assert_eq!("bd", cells(&mut grid.neighbors4(0, 0)));
+        assert_eq!("efh", cells(&mut grid.neighbors8(2, 2)));
+    }
+
+    #[test]
+    pub fn test_display_map() {
+        let grid = CONTENT.parse::<Grid<char>>().unwrap();
+        assert_eq!(CONTENT.trim_end(), grid.to_string());
+
+        let mut grid = grid.map(|c| *c as u8 - b'a');
+        *grid.get_mut(1, 1).unwrap() = 0;
+        assert_eq!("012\n305\n678", grid.to_string());
+        assert_eq!(
+            Some(((2, 2), &8)),
+            grid.iter().max_by_key(|(_, cell)| **cell)
+        );
+    }
+}
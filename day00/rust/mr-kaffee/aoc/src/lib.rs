@@ -1,5 +1,43 @@
+use color::{paint, Color};
 use err::PuzzleError;
-use std::time::Instant;
+use std::{
+    sync::atomic::{AtomicU8, Ordering},
+    time::{Duration, Instant},
+};
+use summary::{RunSummary, SummaryRow};
+
+/// name of the environment variable enabling quiet mode, see [`is_quiet`]
+pub const QUIET_ENV: &str = "AOC_QUIET";
+
+/// quiet mode set with [`set_quiet`], `0` if not set
+static QUIET: AtomicU8 = AtomicU8::new(0);
+
+/// enable or disable quiet mode, overriding the environment variable [`QUIET_ENV`]
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(if quiet { 2 } else { 1 }, Ordering::Relaxed);
+}
+
+/// check whether quiet mode is enabled
+///
+/// In quiet mode, solving a puzzle prints the results of its stars only, one per line, which
+/// makes it easy to capture them in scripts. Errors are still printed. Quiet mode is enabled
+/// with [`set_quiet`] or by setting the environment variable [`QUIET_ENV`] to a non-empty value
+/// other than `0`.
+///
+/// # Examples
+/// ```
+/// # use mr_kaffee_aoc::{is_quiet, set_quiet};
+/// set_quiet(true);
+/// assert!(is_quiet());
+/// set_quiet(false);
+/// assert!(!is_quiet());
+/// ```
+pub fn is_quiet() -> bool {
+    match QUIET.load(Ordering::Relaxed) {
+        0 => std::env::var(QUIET_ENV).is_ok_and(|v| !v.is_empty() && v != "0"),
+        quiet => quiet == 2,
+    }
+}
 
 /// Trait representing the result of a puzzle
 ///
@@ -48,7 +86,7 @@ where
 /// The solution for an [Advent of Code](https:://adventofcode.com) puzzle of a specific day/year
 pub struct Puzzle<'a, S, T1, R1, T2, R2>
 where
-    S: 'static + TryFrom<&'a str>,
+    S: 'a + TryFrom<&'a str>,
     T1: 'static + PartialEq + std::fmt::Display,
     R1: 'static + PuzzleResult<T1>,
     T2: 'static + PartialEq + std::fmt::Display,
@@ -60,16 +98,19 @@ where
     /// day of the puzzle
     pub day: u16,
     /// the puzzle input
+    ///
+    /// The input data `S` may borrow from the input, e.g., to parse zero-copy into `&'a str`
+    /// slices.
     pub input: &'a str,
     /// the first star of the puzzle, if any
-    pub star1: Option<Star<S, T1, R1>>,
+    pub star1: Option<Star<'a, S, T1, R1>>,
     /// the second star of the puzzle, if any
-    pub star2: Option<Star<S, T2, R2>>,
+    pub star2: Option<Star<'a, S, T2, R2>>,
 }
 
 impl<'a, S, T1, R1, T2, R2> Puzzle<'a, S, T1, R1, T2, R2>
 where
-    S: 'static + TryFrom<&'a str>,
+    S: 'a + TryFrom<&'a str>,
     T1: 'static + PartialEq + std::fmt::Display,
     R1: 'static + PuzzleResult<T1>,
     T2: 'static + PartialEq + std::fmt::Display,
@@ -114,31 +155,346 @@ where
     /// let puzzle = Puzzle { input: "20", ..puzzle };
     /// assert!(puzzle.solve().is_err());
     /// ```
+    ///
+    /// The results and timings are printed, see [`Puzzle::compute`] to obtain the results
+    /// without printing anything.
     pub fn solve(&self) -> Result<(Option<T1>, Option<T2>), PuzzleError> {
+        self.solve_timed().map(|report| {
+            (
+                report.star1.map(|r| r.result),
+                report.star2.map(|r| r.result),
+            )
+        })
+    }
+
+    /// Solve a puzzle without printing anything and return the results of its stars
+    ///
+    /// The results are verified against the expected results [`Star::exp`] as in
+    /// [`Puzzle::solve`], so that they can be used programmatically, e.g., to submit them.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_aoc::{Puzzle,Star};
+    /// let puzzle: Puzzle<'_, &str, usize, usize, usize, usize> = Puzzle {
+    ///     year: 2022,
+    ///     day: 24,
+    ///     input: "hello",
+    ///     star1: Some(Star { name: "length", f: &(|v: &&str| v.len()), exp: Some(5) }),
+    ///     star2: Some(Star { name: "double", f: &(|v: &&str| 2 * v.len()), exp: None }),
+    /// };
+    /// assert_eq!((Some(5), Some(10)), puzzle.compute().unwrap());
+    ///
+    /// let puzzle = Puzzle { input: "hi", ..puzzle };
+    /// assert!(puzzle.compute().is_err());
+    /// ```
+    pub fn compute(&self) -> Result<(Option<T1>, Option<T2>), PuzzleError> {
+        let data = self.input.try_into()?;
+
+        let star1 = self.star1.as_ref().map(|s| s.solve(&data)).transpose()?;
+        let star2 = self.star2.as_ref().map(|s| s.solve(&data)).transpose()?;
+
+        Ok((star1, star2))
+    }
+
+    /// Replace the input, e.g., by an input downloaded at runtime
+    ///
+    /// The input does not need to be `'static`, it only has to outlive the puzzle. Generated
+    /// puzzle crates provide `puzzle_with_input` for the same purpose.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_aoc::{Puzzle,Star};
+    /// let input = String::from("hello world");
+    /// let puzzle: Puzzle<'_, &str, usize, usize, usize, usize> = Puzzle {
+    ///     year: 2022,
+    ///     day: 24,
+    ///     input: "",
+    ///     star1: Some(Star { name: "length", f: &(|v: &&str| v.len()), exp: None }),
+    ///     star2: None,
+    /// };
+    /// let puzzle = puzzle.with_input(&input);
+    /// assert_eq!((Some(11), None), puzzle.solve().unwrap());
+    /// ```
+    pub fn with_input(self, input: &'a str) -> Self {
+        Self { input, ..self }
+    }
+
+    /// Solve a puzzle for an `input` other than [`Puzzle::input`], e.g., an example input
+    ///
+    /// The results are not verified against the expected results [`Star::exp`], which refer to
+    /// the puzzle's own input, and nothing is printed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_aoc::{Puzzle,Star};
+    /// let puzzle: Puzzle<'_, &str, usize, usize, usize, usize> = Puzzle {
+    ///     year: 2022,
+    ///     day: 24,
+    ///     input: "hello",
+    ///     star1: Some(Star { name: "length", f: &(|v: &&str| v.len()), exp: Some(5) }),
+    ///     star2: None,
+    /// };
+    /// assert_eq!((Some(3), None), puzzle.solve_with_input("abc").unwrap());
+    /// ```
+    pub fn solve_with_input(
+        &self,
+        input: &'a str,
+    ) -> Result<(Option<T1>, Option<T2>), PuzzleError> {
+        let data = input.try_into()?;
+
+        let star1 = self
+            .star1
+            .as_ref()
+            .map(|s| s.solve_unverified(&data))
+            .transpose()?;
+        let star2 = self
+            .star2
+            .as_ref()
+            .map(|s| s.solve_unverified(&data))
+            .transpose()?;
+
+        Ok((star1, star2))
+    }
+
+    /// Solve a puzzle for an example `input` and verify the results against `exp1` and `exp2`,
+    /// see [`Puzzle::solve_with_input`]
+    ///
+    /// Stars without an expected example result are not verified. A mismatch results in an
+    /// error of kind [`err::Kind::BadResult`] with the star's name and the expected and actual
+    /// results.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_aoc::{err::Kind,Puzzle,Star};
+    /// let puzzle: Puzzle<'_, &str, usize, usize, usize, usize> = Puzzle {
+    ///     year: 2022,
+    ///     day: 24,
+    ///     input: "hello",
+    ///     star1: Some(Star { name: "length", f: &(|v: &&str| v.len()), exp: Some(5) }),
+    ///     star2: Some(Star { name: "double", f: &(|v: &&str| 2 * v.len()), exp: None }),
+    /// };
+    /// assert!(puzzle.assert_example("abc", Some(3), Some(6)).is_ok());
+    /// assert!(puzzle.assert_example("abc", Some(3), None).is_ok());
+    ///
+    /// let err = puzzle.assert_example("abc", Some(3), Some(5)).unwrap_err();
+    /// assert!(matches!(err.kind(), Kind::BadResult("double", _)));
+    /// ```
+    pub fn assert_example(
+        &self,
+        input: &'a str,
+        exp1: Option<T1>,
+        exp2: Option<T2>,
+    ) -> Result<(), PuzzleError> {
+        let (act1, act2) = self.solve_with_input(input)?;
+
+        match (exp1, act1, self.star1.as_ref()) {
+            (Some(exp), Some(act), Some(star)) if exp != act => {
+                return Err(PuzzleError::bad_result(star.name, exp, act))
+            }
+            (Some(_), None, _) => return Err(PuzzleError::missing_star(self.year, self.day, 1)),
+            _ => (),
+        }
+        match (exp2, act2, self.star2.as_ref()) {
+            (Some(exp), Some(act), Some(star)) if exp != act => {
+                Err(PuzzleError::bad_result(star.name, exp, act))
+            }
+            (Some(_), None, _) => Err(PuzzleError::missing_star(self.year, self.day, 2)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Solve a puzzle for several labeled `inputs`, e.g., your own and a friend's, to check that
+    /// a solution generalizes
+    ///
+    /// Every input is solved with [`Puzzle::solve_with_input`], so results are not verified and
+    /// a failing input does not stop the others. The results are returned with their labels in
+    /// the order of the inputs.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_aoc::{Puzzle,Star};
+    /// let puzzle: Puzzle<'_, &str, usize, usize, usize, usize> = Puzzle {
+    ///     year: 2022,
+    ///     day: 24,
+    ///     input: "hello",
+    ///     star1: Some(Star { name: "length", f: &(|v: &&str| v.len()), exp: Some(5) }),
+    ///     star2: Some(Star { name: "double", f: &(|v: &&str| 2 * v.len()), exp: None }),
+    /// };
+    /// let results = puzzle.solve_many(&[("mine", "abc"), ("friend", "abcd")]);
+    /// assert_eq!("friend", results[1].0);
+    /// assert_eq!((Some(4), Some(8)), *results[1].1.as_ref().unwrap());
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn solve_many(
+        &self,
+        inputs: &[(&str, &'a str)],
+    ) -> Vec<(String, Result<(Option<T1>, Option<T2>), PuzzleError>)> {
+        inputs
+            .iter()
+            .map(|(label, input)| (label.to_string(), self.solve_with_input(input)))
+            .collect()
+    }
+
+    /// Solve a puzzle and return a [`SolveReport`] with the results and timings of each star
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_aoc::{Puzzle,Star};
+    /// let puzzle: Puzzle<'_, &str, usize, usize, usize, usize> = Puzzle {
+    ///     year: 2022,
+    ///     day: 24,
+    ///     input: "hello",
+    ///     star1: Some(Star { name: "length", f: &(|v: &&str| v.len()), exp: Some(5) }),
+    ///     star2: None,
+    /// };
+    /// let report = puzzle.solve_timed().unwrap();
+    /// assert_eq!(5, report.star1.unwrap().result);
+    /// assert!(report.star2.is_none());
+    /// ```
+    pub fn solve_timed(&self) -> Result<SolveReport<T1, T2>, PuzzleError> {
+        #[cfg(feature = "peak-alloc")]
+        let baseline = alloc::reset_peak();
         let t = Instant::now();
 
         let data = self.input.try_into()?;
+        let parse_duration = t.elapsed();
 
-        let sol1 = self
+        let star1 = self
             .star1
             .as_ref()
-            .map(|p| p.solve_timed(&data))
+            .map(|p| p.solve_report(&data))
             .transpose()?;
-        let sol2 = self
+        let star2 = self
             .star2
             .as_ref()
-            .map(|p| p.solve_timed(&data))
+            .map(|p| p.solve_report(&data))
             .transpose()?;
 
-        println!(
-            "-> Solved puzzle {:04}/{:02} in {:?}",
-            self.year,
-            self.day,
-            t.elapsed()
-        );
+        let duration = t.elapsed();
+        #[cfg(feature = "peak-alloc")]
+        let peak_memory = alloc::peak().map(|peak| peak.saturating_sub(baseline));
+        #[cfg(feature = "peak-alloc")]
+        let memory = peak_memory
+            .map(|bytes| format!(", peak memory {}", alloc::format_bytes(bytes)))
+            .unwrap_or_default();
+        #[cfg(not(feature = "peak-alloc"))]
+        let (peak_memory, memory) = (None, "");
+        if !is_quiet() {
+            println!(
+                "-> Solved puzzle {:04}/{:02} in {:?} (parse {:?}){memory}",
+                self.year, self.day, duration, parse_duration
+            );
+        }
+
+        Ok(SolveReport {
+            year: self.year,
+            day: self.day,
+            star1,
+            star2,
+            duration,
+            parse_duration,
+            peak_memory,
+        })
+    }
 
-        Ok((sol1, sol2))
+    /// Solve a puzzle with [`Puzzle::solve_timed`] and compare the time of every star against
+    /// a `baseline`, stars which got slower than the threshold are printed, unless quiet mode is
+    /// enabled, and returned
+    ///
+    /// See [`baseline::Baseline`] for details.
+    #[cfg(feature = "json")]
+    pub fn solve_timed_baseline(
+        &self,
+        baseline: &baseline::Baseline,
+    ) -> Result<(SolveReport<T1, T2>, Vec<baseline::Regression>), PuzzleError> {
+        let report = self.solve_timed()?;
+        let regressions = baseline.check_report(&report)?;
+        if !is_quiet() {
+            for regression in &regressions {
+                println!("{}", paint(format!("-> {regression}"), Color::Red));
+            }
+        }
+        Ok((report, regressions))
     }
+
+    /// Solve a puzzle and return the results as JSON
+    ///
+    /// The JSON object contains the `year`, the `day` and objects `star1` and `star2` (`null` if
+    /// the star does not exist) with the `name`, the `result`, the `expected` result (`null` if
+    /// not specified), whether the result `matches` the expected result (`null` if not
+    /// specified), and the duration in milliseconds `duration_ms`. Results are formatted into
+    /// strings. A result not matching the expected result does not cause an error.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_aoc::{Puzzle,Star};
+    /// let puzzle: Puzzle<'_, &str, usize, usize, usize, usize> = Puzzle {
+    ///     year: 2022,
+    ///     day: 24,
+    ///     input: "hello",
+    ///     star1: Some(Star { name: "length", f: &(|v: &&str| v.len()), exp: Some(4) }),
+    ///     star2: None,
+    /// };
+    /// let json = puzzle.solve_to_json().unwrap();
+    /// assert!(json.starts_with(r#"{"day":24,"star1":{"duration_ms":"#));
+    /// assert!(json.ends_with(r#""expected":"4","matches":false,"name":"length","result":"5"},"star2":null,"year":2022}"#));
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn solve_to_json(&self) -> Result<String, PuzzleError> {
+        let data = self.input.try_into()?;
+
+        let star1 = self
+            .star1
+            .as_ref()
+            .map(|s| s.solve_json(&data))
+            .transpose()?;
+        let star2 = self
+            .star2
+            .as_ref()
+            .map(|s| s.solve_json(&data))
+            .transpose()?;
+
+        Ok(serde_json::json!({
+            "year": self.year,
+            "day": self.day,
+            "star1": star1,
+            "star2": star2,
+        })
+        .to_string())
+    }
+}
+
+/// Report on a solved [`Puzzle`] returned by [`Puzzle::solve_timed`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveReport<T1, T2> {
+    /// year of the puzzle
+    pub year: u16,
+    /// day of the puzzle
+    pub day: u16,
+    /// report for the first star, if any
+    pub star1: Option<StarReport<T1>>,
+    /// report for the second star, if any
+    pub star2: Option<StarReport<T2>>,
+    /// total time including parsing the input
+    pub duration: Duration,
+    /// time it took to parse the input
+    pub parse_duration: Duration,
+    /// peak bytes allocated while solving, only available with the `peak-alloc` feature if
+    /// [`alloc::PeakAlloc`] is the global allocator
+    pub peak_memory: Option<usize>,
+}
+
+/// Report on a solved [`Star`] returned by [`Star::solve_report`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StarReport<T> {
+    /// the name of the star
+    pub name: &'static str,
+    /// the result
+    pub result: T,
+    /// `true` if the result was verified against an expected result
+    pub verified: bool,
+    /// time it took to solve the star
+    pub duration: Duration,
 }
 
 /// Generic view on puzzles not depending on any internal types
@@ -147,8 +503,45 @@ pub trait GenericPuzzle {
     fn solve_handle_err(&self) -> bool;
 
     /// solve a puzzle and forward errors to caller
+    ///
+    /// Results of stars with an expected result are verified, a mismatch is reported as an
+    /// error of kind [`err::Kind::BadResult`].
     fn solve_report_err(&self) -> Result<(), PuzzleError>;
 
+    /// solve a puzzle without printing and return the verified results of both stars formatted
+    /// into `String`s, see [`Puzzle::compute`]
+    fn solve_results(&self) -> Result<(Option<String>, Option<String>), PuzzleError>;
+
+    /// solve a puzzle without printing and return the verified results of both stars formatted
+    /// with [`Display`](std::fmt::Display), whatever their types, see
+    /// [`GenericPuzzle::solve_results`]
+    ///
+    /// It returns an `Err<PuzzleError>` of kind [`err::Kind::MissingStar`] if a star is not
+    /// implemented.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_aoc::{GenericPuzzle,Puzzle,Star};
+    /// let puzzle: Puzzle<'_, &str, usize, usize, String, String> = Puzzle {
+    ///     year: 2022,
+    ///     day: 24,
+    ///     input: "hello",
+    ///     star1: Some(Star { name: "length", f: &(|v: &&str| v.len()), exp: Some(5) }),
+    ///     star2: Some(Star { name: "upper", f: &(|v: &&str| v.to_uppercase()), exp: None }),
+    /// };
+    /// assert_eq!(("5".to_string(), "HELLO".to_string()), puzzle.solve_to_strings().unwrap());
+    ///
+    /// let puzzle = Puzzle { star2: None, ..puzzle };
+    /// assert!(puzzle.solve_to_strings().is_err());
+    /// ```
+    fn solve_to_strings(&self) -> Result<(String, String), PuzzleError> {
+        match self.solve_results()? {
+            (Some(star1), Some(star2)) => Ok((star1, star2)),
+            (None, _) => Err(PuzzleError::missing_star(self.year(), self.day(), 1)),
+            (_, None) => Err(PuzzleError::missing_star(self.year(), self.day(), 2)),
+        }
+    }
+
     /// get the result for star 1 formatted into a `String`
     ///
     /// This functions returns Ok(None) if no solution is implemented.
@@ -161,11 +554,32 @@ pub trait GenericPuzzle {
     /// It returns an `Err<PuzzleError>` if an error occurs while solving the puzzle
     fn solve_star_2(&self) -> Result<Option<String>, PuzzleError>;
 
+    /// solve a single star (`1` or `2`), print the result with timing and return it formatted
+    /// into a `String`
+    ///
+    /// It returns an `Err<PuzzleError>` of kind [`err::Kind::MissingStar`] if the star is not
+    /// implemented or another `Err<PuzzleError>` if an error occurs while solving the puzzle
+    fn solve_star(&self, star: u8) -> Result<String, PuzzleError>;
+
+    /// solve a puzzle without printing and add a row for every star to `summary`
+    ///
+    /// Errors are not forwarded but reported as rows with [`summary::Status::Error`].
+    fn solve_summary(&self, summary: &mut RunSummary);
+
     /// get the year of the puzzle
     fn year(&self) -> u16;
 
     /// get the day of the puzzle
     fn day(&self) -> u16;
+
+    /// check whether the puzzle has a first star, nothing is solved
+    fn has_star1(&self) -> bool;
+
+    /// check whether the puzzle has a second star, nothing is solved
+    fn has_star2(&self) -> bool;
+
+    /// get the names of the stars the puzzle has, nothing is solved
+    fn star_names(&self) -> Vec<&'static str>;
 }
 
 /// if [`GenericPuzzle`] is implemented for a type `T` also implement it for `&T`
@@ -181,6 +595,10 @@ where
         T::solve_report_err(&self)
     }
 
+    fn solve_results(&self) -> Result<(Option<String>, Option<String>), PuzzleError> {
+        T::solve_results(self)
+    }
+
     fn solve_star_1(&self) -> Result<Option<String>, PuzzleError> {
         T::solve_star_1(&self)
     }
@@ -189,6 +607,14 @@ where
         T::solve_star_2(&self)
     }
 
+    fn solve_star(&self, star: u8) -> Result<String, PuzzleError> {
+        T::solve_star(self, star)
+    }
+
+    fn solve_summary(&self, summary: &mut RunSummary) {
+        T::solve_summary(self, summary)
+    }
+
     fn year(&self) -> u16 {
         T::year(&self)
     }
@@ -196,11 +622,23 @@ where
     fn day(&self) -> u16 {
         T::day(&self)
     }
+
+    fn has_star1(&self) -> bool {
+        T::has_star1(self)
+    }
+
+    fn has_star2(&self) -> bool {
+        T::has_star2(self)
+    }
+
+    fn star_names(&self) -> Vec<&'static str> {
+        T::star_names(self)
+    }
 }
 
 impl<'a, S, T1, R1, T2, R2> GenericPuzzle for Puzzle<'a, S, T1, R1, T2, R2>
 where
-    S: 'static + TryFrom<&'a str>,
+    S: 'a + TryFrom<&'a str>,
     T1: 'static + PartialEq + std::fmt::Display,
     R1: 'static + PuzzleResult<T1>,
     T2: 'static + PartialEq + std::fmt::Display,
@@ -213,7 +651,8 @@ where
         let result = self.solve();
         match result {
             Err(e) => {
-                println!("Error solving puzzle {}/{}: {}", self.year, self.day, e);
+                let line = format!("Error solving puzzle {}/{}: {}", self.year, self.day, e);
+                println!("{}", paint(line, Color::Red));
                 false
             }
             _ => true,
@@ -226,6 +665,11 @@ where
         self.solve().map(|_| ())
     }
 
+    fn solve_results(&self) -> Result<(Option<String>, Option<String>), PuzzleError> {
+        let (star1, star2) = self.compute()?;
+        Ok((star1.map(|r| r.to_string()), star2.map(|r| r.to_string())))
+    }
+
     fn solve_star_1(&self) -> Result<Option<String>, PuzzleError> {
         let data = self.input.try_into()?;
 
@@ -244,6 +688,75 @@ where
             .transpose()
     }
 
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_aoc::{err::Kind,GenericPuzzle,Puzzle,Star};
+    /// let puzzle: Puzzle<'_, &str, usize, usize, usize, usize> = Puzzle {
+    ///     year: 2022,
+    ///     day: 24,
+    ///     input: "hello",
+    ///     star1: None,
+    ///     star2: Some(Star { name: "length", f: &(|v: &&str| v.len()), exp: None }),
+    /// };
+    /// assert_eq!("5", puzzle.solve_star(2).unwrap());
+    /// assert!(matches!(puzzle.solve_star(1).unwrap_err().kind(), Kind::MissingStar(1)));
+    /// assert!(puzzle.solve_star(3).is_err());
+    /// ```
+    fn solve_star(&self, star: u8) -> Result<String, PuzzleError> {
+        if !(1..=2).contains(&star) {
+            return Err(format!("Illegal star {star}, expected 1 or 2").into());
+        }
+
+        let data = self.input.try_into()?;
+
+        let result = match star {
+            1 => self
+                .star1
+                .as_ref()
+                .map(|s| s.solve_timed(&data).map(|r| r.to_string())),
+            _ => self
+                .star2
+                .as_ref()
+                .map(|s| s.solve_timed(&data).map(|r| r.to_string())),
+        };
+
+        result.unwrap_or_else(|| Err(PuzzleError::missing_star(self.year, self.day, star)))
+    }
+
+    fn solve_summary(&self, summary: &mut RunSummary) {
+        let t = Instant::now();
+        let data: Result<S, _> = self.input.try_into();
+        let data = match data {
+            Ok(data) => data,
+            Err(err) => {
+                let err = PuzzleError::from(err);
+                for star in [self.star1.is_some(), self.star2.is_some()]
+                    .into_iter()
+                    .zip(1..)
+                    .filter_map(|(some, star)| some.then_some(star))
+                {
+                    summary.push(SummaryRow {
+                        year: self.year,
+                        day: self.day,
+                        star,
+                        result: err.to_string(),
+                        expected: None,
+                        duration: t.elapsed(),
+                        status: summary::Status::Error,
+                    });
+                }
+                return;
+            }
+        };
+
+        if let Some(star) = &self.star1 {
+            summary.push(star.summary_row(self.year, self.day, 1, &data));
+        }
+        if let Some(star) = &self.star2 {
+            summary.push(star.summary_row(self.year, self.day, 2, &data));
+        }
+    }
+
     fn year(&self) -> u16 {
         self.year
     }
@@ -251,30 +764,122 @@ where
     fn day(&self) -> u16 {
         self.day
     }
+
+    fn has_star1(&self) -> bool {
+        self.star1.is_some()
+    }
+
+    fn has_star2(&self) -> bool {
+        self.star2.is_some()
+    }
+
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_aoc::{GenericPuzzle,Puzzle,Star};
+    /// let puzzle: Puzzle<'_, &str, usize, usize, usize, usize> = Puzzle {
+    ///     year: 2022,
+    ///     day: 24,
+    ///     input: "hello",
+    ///     star1: None,
+    ///     star2: Some(Star { name: "length", f: &(|v: &&str| v.len()), exp: None }),
+    /// };
+    /// assert!(!puzzle.has_star1());
+    /// assert!(puzzle.has_star2());
+    /// assert_eq!(vec!["length"], puzzle.star_names());
+    /// ```
+    fn star_names(&self) -> Vec<&'static str> {
+        let star1 = self.star1.as_ref().map(|star| star.name);
+        let star2 = self.star2.as_ref().map(|star| star.name);
+        star1.into_iter().chain(star2).collect()
+    }
 }
 
 /// type for solver functions used for [`Star::f`]
 pub type SolverFun<IN, R> = dyn Fn(&IN) -> R;
 
+/// Context computed once from the parsed input and shared by both stars, see [`Prepared`]
+pub trait Prepare<S>: Sized {
+    /// compute the context from the parsed input `data`
+    fn prepare(data: &S) -> Result<Self, PuzzleError>;
+}
+
+/// Parsed input `S` together with a context `C` computed from it with [`Prepare::prepare`]
+///
+/// If both stars need an expensive intermediate result, use `Prepared<S, C>` as input data of
+/// the [`Puzzle`]. The context is computed once when the input is parsed and both stars receive
+/// it together with the parsed input. Days not needing a context just use `S`.
+///
+/// # Examples
+/// ```
+/// # use mr_kaffee_aoc::{err::PuzzleError, Prepare, Prepared, Puzzle, Star};
+/// # use std::sync::atomic::{AtomicUsize, Ordering};
+/// static PREPARED: AtomicUsize = AtomicUsize::new(0);
+///
+/// struct Sorted(Vec<char>);
+///
+/// impl Prepare<&str> for Sorted {
+///     fn prepare(data: &&str) -> Result<Self, PuzzleError> {
+///         PREPARED.fetch_add(1, Ordering::Relaxed);
+///         let mut chars = data.chars().collect::<Vec<_>>();
+///         chars.sort_unstable();
+///         Ok(Self(chars))
+///     }
+/// }
+///
+/// let puzzle: Puzzle<'_, Prepared<&str, Sorted>, char, char, char, char> = Puzzle {
+///     year: 2022,
+///     day: 24,
+///     input: "hello",
+///     star1: Some(Star { name: "min", f: &(|p: &Prepared<_, Sorted>| p.ctx.0[0]), exp: Some('e') }),
+///     star2: Some(Star { name: "max", f: &(|p: &Prepared<_, Sorted>| p.ctx.0[4]), exp: Some('o') }),
+/// };
+/// assert!(puzzle.solve().is_ok());
+/// assert_eq!(1, PREPARED.load(Ordering::Relaxed));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Prepared<S, C> {
+    /// the parsed input
+    pub data: S,
+    /// the context computed from the parsed input
+    pub ctx: C,
+}
+
+impl<'a, S, C> TryFrom<&'a str> for Prepared<S, C>
+where
+    S: TryFrom<&'a str>,
+    C: Prepare<S>,
+    PuzzleError: From<<S as TryFrom<&'a str>>::Error>,
+{
+    type Error = PuzzleError;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        let data = S::try_from(s)?;
+        let ctx = C::prepare(&data)?;
+        Ok(Self { data, ctx })
+    }
+}
+
 /// The solution for one star for a specific [`Puzzle`]
-pub struct Star<S, T, R>
+pub struct Star<'a, S, T, R>
 where
-    S: 'static,
+    S: 'a,
     T: 'static + PartialEq + std::fmt::Display,
     R: 'static + PuzzleResult<T>,
 {
     /// the name of the star
     pub name: &'static str,
     /// the solver function
-    pub f: &'static SolverFun<S, R>,
+    pub f: &'a SolverFun<S, R>,
     /// the expected result, if [`Option::None`], no result verification is performed in the
-    /// solve functions [`Star::solve`] and [`Star::solve_timed`].
+    /// solve functions [`Star::solve`] and [`Star::solve_timed`]. Otherwise, a mismatch results
+    /// in an error of kind [`err::Kind::BadResult`] with the star's name and the expected and
+    /// actual results.
     pub exp: Option<T>,
 }
 
-impl<S, T, R> Star<S, T, R>
+impl<'a, S, T, R> Star<'a, S, T, R>
 where
-    S: 'static,
+    S: 'a,
     T: 'static + PartialEq + std::fmt::Display,
     R: 'static + PuzzleResult<T>,
 {
@@ -288,8 +893,17 @@ where
     /// assert_eq!(10, star.solve(&10).unwrap());
     /// assert!(star.solve(&8).is_err());
     /// ```
+    ///
+    /// If the solver function returns an error, the error is forwarded with the star's name added
+    /// to its message.
+    ///
+    /// ```
+    /// # use mr_kaffee_aoc::Star;
+    /// let star = Star { name: "my star", f: &(|v: &usize| if *v > 0 { Ok(*v) } else { Err("zero") }), exp: None };
+    /// assert_eq!("my star: zero", star.solve(&0).err().unwrap().to_string());
+    /// ```
     pub fn solve(&self, data: &S) -> Result<T, PuzzleError> {
-        match ((self.f)(data).result(), self.exp.as_ref()) {
+        match (self.solve_unverified(data), self.exp.as_ref()) {
             (Ok(act), Some(exp)) if exp != &act => {
                 // expected result specified but does not match
                 Err(PuzzleError::bad_result(self.name, exp, &act))
@@ -298,6 +912,14 @@ where
         }
     }
 
+    /// solve a star without verifying the result, errors are forwarded with the star's name
+    /// added to their message
+    fn solve_unverified(&self, data: &S) -> Result<T, PuzzleError> {
+        (self.f)(data)
+            .result()
+            .map_err(|err| err.with_context(self.name))
+    }
+
     /// solve a star & verify result against expected result [`Star::exp`] if not [`Option::None`].
     /// Measure time it takes to solve the puzzle and print to standard out.
     ///
@@ -311,15 +933,82 @@ where
     /// assert!(star.solve_timed(&8).is_err());
     /// ```
     pub fn solve_timed(&self, data: &S) -> Result<T, PuzzleError> {
+        self.solve_report(data).map(|report| report.result)
+    }
+
+    /// solve a star like [`Star::solve_timed`] and return a [`StarReport`] with the result and
+    /// the time it took to solve the star.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_aoc::Star;
+    /// let star = Star { name: "my star", f: &(|v: &usize| *v), exp: None };
+    /// let report = star.solve_report(&10).unwrap();
+    /// assert_eq!("my star", report.name);
+    /// assert_eq!(10, report.result);
+    /// assert!(!report.verified);
+    /// ```
+    pub fn solve_report(&self, data: &S) -> Result<StarReport<T>, PuzzleError> {
         let t = Instant::now();
         let sol = self.solve(data)?;
-        let v = if self.exp.is_none() {
-            " (unverified)"
+        let duration = t.elapsed();
+        let line = format!("Solved {} in {:?}: {}", self.name, duration, sol);
+        if is_quiet() {
+            println!("{sol}");
+        } else if self.exp.is_none() {
+            println!("{line} (unverified)");
         } else {
-            ""
+            println!("{}", paint(line, Color::Green));
+        }
+        Ok(StarReport {
+            name: self.name,
+            result: sol,
+            verified: self.exp.is_some(),
+            duration,
+        })
+    }
+
+    /// solve a star without printing and create a row for a [`RunSummary`]
+    fn summary_row(&self, year: u16, day: u16, star: u8, data: &S) -> SummaryRow {
+        let t = Instant::now();
+        let act = (self.f)(data).result();
+        let duration = t.elapsed();
+
+        let (result, status) = match (act, self.exp.as_ref()) {
+            (Ok(act), Some(exp)) if exp == &act => (act.to_string(), summary::Status::Passed),
+            (Ok(act), Some(_)) => (act.to_string(), summary::Status::Failed),
+            (Ok(act), None) => (act.to_string(), summary::Status::Unverified),
+            (Err(err), _) => (err.to_string(), summary::Status::Error),
         };
-        println!("Solved {} in {:?}: {}{}", self.name, t.elapsed(), sol, v);
-        Ok(sol)
+
+        SummaryRow {
+            year,
+            day,
+            star,
+            result,
+            expected: self.exp.as_ref().map(|exp| exp.to_string()),
+            duration,
+            status,
+        }
+    }
+
+    /// solve a star without failing on unexpected results and create JSON value, see
+    /// [`Puzzle::solve_to_json`]
+    #[cfg(feature = "json")]
+    fn solve_json(&self, data: &S) -> Result<serde_json::Value, PuzzleError> {
+        let t = Instant::now();
+        let act = (self.f)(data)
+            .result()
+            .map_err(|err| err.with_context(self.name))?;
+        let duration = t.elapsed();
+
+        Ok(serde_json::json!({
+            "name": self.name,
+            "result": act.to_string(),
+            "expected": self.exp.as_ref().map(|exp| exp.to_string()),
+            "matches": self.exp.as_ref().map(|exp| exp == &act),
+            "duration_ms": duration.as_secs_f64() * 1_000.0,
+        }))
     }
 }
 
@@ -343,8 +1032,14 @@ pub mod err {
         IoError(std::io::Error),
         /// error caused by other [`Error`]s
         GenericError(Box<dyn Error>),
-        /// error caused by a bad result
+        /// error caused by a bad result, i.e., a wrong answer for a star
         BadResult(&'static str, String),
+        /// puzzle for year and day is not yet unlocked
+        Locked(u16, u16),
+        /// error parsing the input, described by a message
+        ParseError(String),
+        /// a star which is not implemented is requested
+        MissingStar(u8),
         /// other unspecified error
         Other,
     }
@@ -393,6 +1088,21 @@ pub mod err {
     /// let err = PuzzleError::bad_result("star 1", 10, 20);
     /// assert!(matches!(err.kind(), Kind::BadResult(_,_)));
     ///
+    /// // parse error
+    /// let err = PuzzleError::parse_error("no calories in line 3");
+    /// assert!(matches!(err.kind(), Kind::ParseError(_)));
+    /// assert_eq!("Parse error: no calories in line 3", err.to_string());
+    ///
+    /// // missing star
+    /// let err = PuzzleError::missing_star(2022, 1, 2);
+    /// assert!(matches!(err.kind(), Kind::MissingStar(2)));
+    /// assert_eq!("Star 2 not implemented for 2022/1", err.to_string());
+    ///
+    /// // locked puzzle
+    /// let err = PuzzleError::locked(2022, 25);
+    /// assert!(matches!(err.kind(), Kind::Locked(2022, 25)));
+    /// assert_eq!("day 25 is not yet unlocked for 2022", err.to_string());
+    ///
     /// // generic error
     /// let msg = "a `&str` message";
     /// let err: PuzzleError = msg.into();
@@ -433,6 +1143,25 @@ pub mod err {
             &self.kind
         }
 
+        /// prefix the message of the error with `context`
+        ///
+        /// # Examples
+        /// ```
+        /// # use mr_kaffee_aoc::err::PuzzleError;
+        /// let err = PuzzleError::from("something failed").with_context("star 1");
+        /// assert_eq!("star 1: something failed", err.to_string());
+        /// ```
+        pub fn with_context<S: AsRef<str>>(self, context: S) -> Self {
+            let message = match self.message {
+                Some(message) => format!("{}: {}", context.as_ref(), message),
+                None => context.as_ref().to_string(),
+            };
+            Self {
+                message: Some(message),
+                ..self
+            }
+        }
+
         /// construct an instance for a bad result
         pub fn bad_result<T>(star_name: &'static str, exp: T, act: T) -> Self
         where
@@ -443,6 +1172,31 @@ pub mod err {
                 message: Some("Unexpected result".into()),
             }
         }
+
+        /// construct an instance for an input that cannot be parsed
+        pub fn parse_error<S: Into<String>>(message: S) -> Self {
+            let message = message.into();
+            Self {
+                message: Some(format!("Parse error: {message}")),
+                kind: Kind::ParseError(message),
+            }
+        }
+
+        /// construct an instance for a star that is not implemented for a puzzle
+        pub fn missing_star(year: u16, day: u16, star: u8) -> Self {
+            Self {
+                kind: Kind::MissingStar(star),
+                message: Some(format!("Star {star} not implemented for {year}/{day}")),
+            }
+        }
+
+        /// construct an instance for a puzzle that is not yet unlocked
+        pub fn locked(year: u16, day: u16) -> Self {
+            Self {
+                kind: Kind::Locked(year, day),
+                message: Some(format!("day {day} is not yet unlocked for {year}")),
+            }
+        }
     }
 
     impl Error for PuzzleError {
@@ -511,6 +1265,77 @@ pub mod err {
             panic!("Unreachable code!");
         }
     }
+
+    /// error parsing a puzzle input, converts into a [`PuzzleError`] of kind
+    /// [`Kind::ParseError`]
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_aoc::err::{Kind, ParseError, PuzzleError};
+    /// let input = "1000\n2000\nabc\n";
+    /// let err = ParseError::unexpected_token(input, 10, "abc");
+    /// assert_eq!(
+    ///     ParseError::UnexpectedToken { line: 3, col: 1, found: "abc".to_string() },
+    ///     err
+    /// );
+    ///
+    /// let err = PuzzleError::from(err);
+    /// assert!(matches!(err.kind(), Kind::ParseError(_)));
+    /// assert_eq!("Parse error: unexpected 'abc' at line 3, column 1", err.to_string());
+    /// ```
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum ParseError {
+        /// the input is empty
+        Empty,
+        /// unexpected token at a `line` and column `col`, both starting at 1
+        UnexpectedToken {
+            line: usize,
+            col: usize,
+            found: String,
+        },
+    }
+
+    impl ParseError {
+        /// construct an instance for an unexpected token `found` at byte `offset` into `input`,
+        /// the offset is converted to a line and a column counting characters
+        pub fn unexpected_token<S: Into<String>>(input: &str, offset: usize, found: S) -> Self {
+            // offsets which are not on a character boundary are moved to the preceding one
+            let mut offset = offset.min(input.len());
+            while !input.is_char_boundary(offset) {
+                offset -= 1;
+            }
+            let before = &input[..offset];
+            let line = before.matches('\n').count() + 1;
+            let col = before[before.rfind('\n').map_or(0, |pos| pos + 1)..]
+                .chars()
+                .count()
+                + 1;
+            Self::UnexpectedToken {
+                line,
+                col,
+                found: found.into(),
+            }
+        }
+    }
+
+    impl fmt::Display for ParseError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Self::Empty => write!(f, "empty input"),
+                Self::UnexpectedToken { line, col, found } => {
+                    write!(f, "unexpected '{found}' at line {line}, column {col}")
+                }
+            }
+        }
+    }
+
+    impl Error for ParseError {}
+
+    impl From<ParseError> for PuzzleError {
+        fn from(err: ParseError) -> Self {
+            Self::parse_error(err.to_string())
+        }
+    }
 }
 
 #[cfg(test)]
@@ -578,12 +1403,69 @@ mod test {
         PUZZLE_OK.solve().unwrap();
     }
 
+    #[test]
+    fn test_puzzle_solve_results() {
+        assert_eq!((Some(100), Some(200)), PUZZLE_OK.compute().unwrap());
+        assert_eq!(
+            (Some("100".to_string()), Some("200".to_string())),
+            PUZZLE_OK.solve_results().unwrap()
+        );
+        let err = PUZZLE_FAIL.solve_results().unwrap_err();
+        assert!(matches!(err.kind(), err::Kind::BadResult("part 1", _)));
+    }
+
+    #[test]
+    fn test_puzzle_solve_with_input() {
+        // expected results refer to the puzzle's own input and are not verified
+        assert_eq!(
+            (Some(200), Some(100)),
+            PUZZLE_OK.solve_with_input("200,100").unwrap()
+        );
+        let err = PUZZLE_OK.solve_with_input("").unwrap_err();
+        assert!(matches!(err.kind(), err::Kind::ParseIntError(_)));
+    }
+
+    #[test]
+    fn test_puzzle_assert_example() {
+        assert!(PUZZLE_OK.assert_example("1,2,3", Some(1), Some(3)).is_ok());
+
+        let err = PUZZLE_OK
+            .assert_example("1,2,3", Some(2), None)
+            .unwrap_err();
+        match err.kind() {
+            err::Kind::BadResult(name, message) => {
+                assert_eq!("part 1", *name);
+                assert_eq!("expected 2, got 1", message);
+            }
+            kind => panic!("Expected bad result error, found {:?}", kind),
+        }
+
+        let puzzle = Puzzle {
+            star2: None,
+            ..PUZZLE_OK
+        };
+        let err = puzzle.assert_example("1,2,3", None, Some(3)).unwrap_err();
+        assert!(matches!(err.kind(), err::Kind::MissingStar(2)));
+    }
+
     #[test]
     fn test_puzzle_fail() {
         let result = PUZZLE_FAIL.solve();
         assert!(result.is_err(), "Expected error, {:?}", result);
     }
 
+    #[test]
+    fn test_puzzle_fail_report_err() {
+        let result = PUZZLE_FAIL.solve_report_err();
+        match result.as_ref().map_err(|err| err.kind()) {
+            Err(err::Kind::BadResult(name, message)) => {
+                assert_eq!("part 1", *name);
+                assert_eq!("expected 100, got 200", message);
+            }
+            _ => panic!("Expected bad result error, found {:?}", result),
+        }
+    }
+
     #[test]
     fn test_star_fail_bad_result() {
         let star = PUZZLE_OK.star1.expect("No star1");
@@ -613,7 +1495,8 @@ mod test {
             result.is_err(),
             "Result is expected to be an error (no points). Found {:?}",
             result
-        )
+        );
+        assert_eq!("part 2: No last point", result.err().unwrap().to_string());
     }
 
     #[test]
@@ -642,12 +1525,99 @@ mod test {
 
         Ok(())
     }
+
+    struct BorrowedData<'a> {
+        words: Vec<&'a str>,
+    }
+
+    impl<'a> From<&'a str> for BorrowedData<'a> {
+        fn from(s: &'a str) -> Self {
+            Self {
+                words: s.split(',').collect(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_puzzle_borrowed_data() {
+        // input which is not 'static, parsed without copying
+        let input = String::from("hello,world");
+        let longest = |data: &BorrowedData| data.words.iter().map(|w| w.len()).max().unwrap();
+        let first = |data: &BorrowedData| data.words[0].to_string();
+        let puzzle = Puzzle {
+            year: 2022,
+            day: 0,
+            input: &input,
+            star1: Some(Star {
+                name: "part 1",
+                f: &longest,
+                exp: Some(5),
+            }),
+            star2: Some(Star {
+                name: "part 2",
+                f: &first,
+                exp: Some("hello".to_string()),
+            }),
+        };
+
+        puzzle.solve().unwrap();
+        assert_eq!("hello", puzzle.solve_star(2).unwrap());
+    }
+
+    #[test]
+    fn test_parse_error_multi_byte() {
+        use crate::err::ParseError;
+
+        // umlauts take two bytes, offset 6 is within the 'ö' starting at offset 5
+        let err = ParseError::unexpected_token("ä\näöx", 6, "ö");
+        assert_eq!(
+            ParseError::UnexpectedToken {
+                line: 2,
+                col: 2,
+                found: "ö".to_string()
+            },
+            err
+        );
+        let err = ParseError::unexpected_token("ä\näöx", 7, "x");
+        assert!(matches!(
+            err,
+            ParseError::UnexpectedToken {
+                line: 2,
+                col: 3,
+                ..
+            }
+        ));
+    }
 }
 
 #[cfg(feature = "io")]
 pub mod puzzle_io;
 
+#[cfg(feature = "async")]
+pub mod async_io;
+
 #[cfg(feature = "template")]
 pub mod template;
 
-pub mod letters;
\ No newline at end of file
+#[cfg(feature = "template")]
+pub mod config;
+
+#[cfg(feature = "testing")]
+pub mod testing;
+
+pub mod letters;
+
+#[cfg(feature = "grid")]
+pub mod grid;
+
+#[cfg(feature = "peak-alloc")]
+pub mod alloc;
+
+#[cfg(feature = "json")]
+pub mod baseline;
+
+pub mod color;
+
+pub mod registry;
+
+pub mod summary;
@@ -1,13 +1,131 @@
-use crate::err::PuzzleError;
+use crate::err::{Kind, PuzzleError};
 use regex::Regex;
-use reqwest::{blocking::RequestBuilder, StatusCode};
-use std::{env, fs, path::Path};
+use reqwest::{
+    blocking::{Client, RequestBuilder},
+    Proxy, StatusCode, Url,
+};
+use std::{
+    env, fs,
+    path::Path,
+    sync::OnceLock,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// first year of AoC
+pub const FIRST_YEAR: u16 = 2015;
+
+/// part of the response body served for puzzles which are not yet unlocked
+const LOCKED_MARKER: &str = "the link will be enabled";
+
+/// part of the pages served to logged in users, see [`PuzzleIO::check_session`]
+const LOGGED_IN_MARKER: &str = "[Log Out]";
+
+/// part of the pages served to anonymous users, see [`PuzzleIO::check_session`]
+const LOGGED_OUT_MARKER: &str = "[Log In]";
+
+/// Fail if network access is disabled with the `no-network` feature
+///
+/// The feature is meant for test builds of downstream crates, e.g., enabled for the
+/// `dev-dependencies` only, so that accidental requests fail immediately with an error of kind
+/// [`std::io::ErrorKind::Unsupported`] instead of hanging or depending on a session cookie.
+pub(crate) fn check_network() -> Result<(), PuzzleError> {
+    if cfg!(feature = "no-network") {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "network disabled in test builds",
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Validate `year` and `day` of a puzzle
+///
+/// The day is expected in the range `1..=25`, the year in the range from [`FIRST_YEAR`] to the
+/// current year plus one. Otherwise, an error of kind [`std::io::ErrorKind::InvalidInput`] is
+/// returned.
+///
+/// # Examples
+/// ```
+/// # use mr_kaffee_aoc::puzzle_io::validate;
+/// assert!(validate(2022, 25).is_ok());
+/// assert!(validate(2022, 0).is_err());
+/// assert!(validate(2014, 1).is_err());
+/// ```
+pub fn validate(year: u16, day: u16) -> Result<(), PuzzleError> {
+    // approximation using the average length of a gregorian year is good enough here
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let max_year = (1970 + secs / 31_556_952 + 1) as u16;
+
+    let message = if !(1..=25).contains(&day) {
+        format!("Invalid day {day}, expected 1..=25")
+    } else if !(FIRST_YEAR..=max_year).contains(&year) {
+        format!("Invalid year {year}, expected {FIRST_YEAR}..={max_year}")
+    } else {
+        return Ok(());
+    };
+
+    Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, message).into())
+}
+
+/// Append path `segments` to `base_url`, every segment is percent-encoded
+///
+/// Trailing slashes of the base URL are ignored, so that `https://example.com/` and
+/// `https://example.com` give the same result.
+///
+/// # Examples
+/// ```
+/// # use mr_kaffee_aoc::puzzle_io::join_url;
+/// let url = join_url("https://example.com/aoc/", ["2022", "day", "1", "a b/c"]).unwrap();
+/// assert_eq!("https://example.com/aoc/2022/day/1/a%20b%2Fc", url.as_str());
+/// assert!(join_url("not a url", ["input"]).is_err());
+/// ```
+pub fn join_url<I>(base_url: &str, segments: I) -> Result<Url, PuzzleError>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let invalid = |reason: &str| -> PuzzleError {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Invalid base URL '{base_url}': {reason}"),
+        )
+        .into()
+    };
+
+    let mut url =
+        Url::parse(base_url.trim_end_matches('/')).map_err(|err| invalid(&err.to_string()))?;
+    url.path_segments_mut()
+        .map_err(|_| invalid("cannot be a base"))?
+        .pop_if_empty()
+        .extend(segments);
+    Ok(url)
+}
 
 /// Puzzle IO
 ///
 /// Create instances using [`PuzzleResult::from`]
 pub struct PuzzleIO {
     pub session: String,
+    /// timeout applied to requests, see [`PuzzleIO::with_timeout`]
+    pub timeout: Duration,
+    /// number of retries on transient failures, see [`PuzzleIO::with_retries`]
+    pub retries: u8,
+    /// delay before the first retry, doubled for every further retry
+    pub retry_delay: Duration,
+    /// value of the `User-Agent` header sent with every request, see
+    /// [`PuzzleIO::with_user_agent`]
+    pub user_agent: String,
+    /// base URL of all requests, see [`PuzzleIO::with_base_url`]
+    pub base_url: String,
+    /// proxy used for all requests, see [`PuzzleIO::with_proxy`]
+    pub(crate) proxy: Option<Proxy>,
+    /// client shared by all requests, built on the first request, see [`PuzzleIO::client`]
+    client: OnceLock<Client>,
 }
 
 impl std::fmt::Debug for PuzzleIO {
@@ -15,6 +133,12 @@ impl std::fmt::Debug for PuzzleIO {
         // do not print session
         f.debug_struct("PuzzleIO")
             .field("session", &format!("{}...", &self.session[0..5]))
+            .field("timeout", &self.timeout)
+            .field("retries", &self.retries)
+            .field("retry_delay", &self.retry_delay)
+            .field("user_agent", &self.user_agent)
+            .field("base_url", &self.base_url)
+            .field("proxy", &self.proxy.is_some())
             .finish()
     }
 }
@@ -22,9 +146,7 @@ impl std::fmt::Debug for PuzzleIO {
 impl From<&str> for PuzzleIO {
     /// Create a [`PuzzleIO`] struct from a `&str` representing a session ID.
     fn from(session: &str) -> Self {
-        Self {
-            session: session.to_string(),
-        }
+        Self::new(session.to_string())
     }
 }
 
@@ -32,41 +154,417 @@ impl TryFrom<&Path> for PuzzleIO {
     type Error = std::io::Error;
 
     fn try_from(path: &Path) -> Result<Self, Self::Error> {
-        Ok(Self {
-            session: fs::read_to_string(path)?.trim().to_string(),
-        })
+        Ok(Self::new(fs::read_to_string(path)?.trim().to_string()))
     }
 }
 
 impl PuzzleIO {
-    fn request_builder(&self, post: bool, year: u16, day: u16, path: &str) -> RequestBuilder {
-        let url = format!("https://adventofcode.com/{year}/day/{day}/{path}");
-        let client = reqwest::blocking::Client::new();
-        if post {
-            client.post(url.as_str())
+    /// name of the environment variable read by [`PuzzleIO::from_env`]
+    pub const SESSION_ENV: &'static str = "AOC_SESSION";
+
+    /// default request timeout
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// default number of retries on transient failures
+    pub const DEFAULT_RETRIES: u8 = 2;
+
+    /// default delay before the first retry
+    pub const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+    /// default base URL
+    pub const DEFAULT_BASE_URL: &'static str = "https://adventofcode.com";
+
+    fn new(session: String) -> Self {
+        Self {
+            session,
+            timeout: Self::DEFAULT_TIMEOUT,
+            retries: Self::DEFAULT_RETRIES,
+            retry_delay: Self::DEFAULT_RETRY_DELAY,
+            user_agent: Self::default_user_agent(),
+            base_url: Self::DEFAULT_BASE_URL.to_string(),
+            proxy: None,
+            client: OnceLock::new(),
+        }
+    }
+
+    /// The default user agent identifies this crate including its repository and authors
+    /// as contact information
+    pub fn default_user_agent() -> String {
+        format!(
+            "{}/{} ({} by {})",
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION"),
+            env!("CARGO_PKG_REPOSITORY"),
+            env!("CARGO_PKG_AUTHORS")
+        )
+    }
+
+    /// Set the value of the `User-Agent` header, which should contain contact information
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_aoc::puzzle_io::PuzzleIO;
+    /// let puzzle_io = PuzzleIO::from("my-session").with_user_agent("my-tool (me@example.com)");
+    /// assert_eq!("my-tool (me@example.com)", puzzle_io.user_agent);
+    /// ```
+    pub fn with_user_agent<S: Into<String>>(self, user_agent: S) -> Self {
+        Self {
+            user_agent: user_agent.into(),
+            ..self
+        }
+    }
+
+    /// Set the timeout applied to requests
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_aoc::puzzle_io::PuzzleIO;
+    /// # use std::time::Duration;
+    /// let puzzle_io = PuzzleIO::from("my-session").with_timeout(Duration::from_secs(5));
+    /// assert_eq!(Duration::from_secs(5), puzzle_io.timeout);
+    /// ```
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        Self {
+            timeout,
+            client: OnceLock::new(),
+            ..self
+        }
+    }
+
+    /// Set the base URL of all requests, e.g., to use a mirror or a local test server
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_aoc::puzzle_io::PuzzleIO;
+    /// let puzzle_io = PuzzleIO::from("my-session").with_base_url("http://localhost:8080/");
+    /// assert_eq!("http://localhost:8080/", puzzle_io.base_url);
+    /// ```
+    pub fn with_base_url<S: Into<String>>(self, base_url: S) -> Self {
+        Self {
+            base_url: base_url.into(),
+            ..self
+        }
+    }
+
+    /// Set a proxy URL used for all requests
+    ///
+    /// Without an explicit proxy, the environment variables `HTTP_PROXY`, `HTTPS_PROXY`, and
+    /// `ALL_PROXY` are honored. Returns an error if the URL is not a valid proxy URL.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_aoc::puzzle_io::PuzzleIO;
+    /// assert!(PuzzleIO::from("my-session").with_proxy("http://proxy.example.com:8080").is_ok());
+    /// assert!(PuzzleIO::from("my-session").with_proxy("not a proxy").is_err());
+    /// ```
+    pub fn with_proxy(self, url: &str) -> Result<Self, PuzzleError> {
+        let proxy = Proxy::all(url)
+            .map_err(|err| PuzzleError::from(format!("Invalid proxy URL '{url}': {err}")))?;
+        Ok(Self {
+            proxy: Some(proxy),
+            client: OnceLock::new(),
+            ..self
+        })
+    }
+
+    /// Set the number of retries and the delay before the first retry used when loading inputs
+    ///
+    /// Network errors and responses with a server error status code (5xx) are retried, the
+    /// delay is doubled with every retry. Responses with a client error status code (4xx)
+    /// fail immediately.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_aoc::puzzle_io::PuzzleIO;
+    /// # use std::time::Duration;
+    /// let puzzle_io = PuzzleIO::from("my-session").with_retries(5, Duration::from_millis(500));
+    /// assert_eq!(5, puzzle_io.retries);
+    /// assert_eq!(Duration::from_millis(500), puzzle_io.retry_delay);
+    /// ```
+    pub fn with_retries(self, retries: u8, retry_delay: Duration) -> Self {
+        Self {
+            retries,
+            retry_delay,
+            ..self
+        }
+    }
+
+    /// Create a [`PuzzleIO`] struct with the session ID read from the environment variable
+    /// [`PuzzleIO::SESSION_ENV`].
+    ///
+    /// Returns an error of kind [`std::io::ErrorKind::NotFound`] if the variable is not set
+    /// or empty.
+    pub fn from_env() -> Result<Self, PuzzleError> {
+        Self::from_env_with(|key| env::var(key).ok())
+    }
+
+    /// create a [`PuzzleIO`] struct with environment variables looked up by `var`, see
+    /// [`PuzzleIO::from_env`]
+    fn from_env_with<F>(var: F) -> Result<Self, PuzzleError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        match var(Self::SESSION_ENV) {
+            Some(session) if !session.trim().is_empty() => {
+                Ok(Self::new(session.trim().to_string()))
+            }
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!(
+                    "Environment variable {} not set or empty",
+                    Self::SESSION_ENV
+                ),
+            )
+            .into()),
+        }
+    }
+
+    /// get the URL for a given `year`, `day`, and `path`, the puzzle page if `path` is empty
+    ///
+    /// See [`join_url`] for how the segments are appended to the base URL.
+    pub(crate) fn url(&self, year: u16, day: u16, path: &str) -> Result<Url, PuzzleError> {
+        let (year, day) = (year.to_string(), day.to_string());
+        let segments = [year.as_str(), "day", day.as_str(), path];
+        join_url(
+            &self.base_url,
+            segments.into_iter().filter(|segment| !segment.is_empty()),
+        )
+    }
+
+    /// get the client shared by all requests to reuse connections
+    ///
+    /// The client is built with the configured timeout and proxy on first use. Changing the
+    /// `timeout` field directly afterwards has no effect, use [`PuzzleIO::with_timeout`]
+    /// instead.
+    pub fn client(&self) -> Result<&Client, PuzzleError> {
+        if let Some(client) = self.client.get() {
+            return Ok(client);
+        }
+
+        let mut builder = Client::builder().timeout(self.timeout);
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(proxy.clone());
+        }
+        let client = builder
+            .build()
+            .map_err(|err| PuzzleError::from(err.to_string()))?;
+        Ok(self.client.get_or_init(|| client))
+    }
+
+    fn request_builder(
+        &self,
+        post: bool,
+        year: u16,
+        day: u16,
+        path: &str,
+    ) -> Result<RequestBuilder, PuzzleError> {
+        let url = self.url(year, day, path)?;
+        let client = self.client()?;
+        Ok(if post {
+            client.post(url)
         } else {
-            client.get(url.as_str())
+            client.get(url)
         }
         .header("Cookie", format!("session={}", self.session))
-        .header(
-            "User-Agent",
-            format!(
-                "{}/{} ({} by {})",
-                env!("CARGO_PKG_NAME"),
-                env!("CARGO_PKG_VERSION"),
-                env!("CARGO_PKG_REPOSITORY"),
-                env!("CARGO_PKG_AUTHORS")
-            ),
-        )
+        .header("User-Agent", &self.user_agent)
+        .header("Accept-Encoding", "identity"))
+    }
+
+    /// map errors from sending requests, timeouts are mapped to errors of kind
+    /// [`std::io::ErrorKind::TimedOut`]
+    pub(crate) fn map_send_err(err: reqwest::Error, year: u16, day: u16) -> PuzzleError {
+        if err.is_timeout() {
+            std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                format!("Request for {year}/{day} timed out: {err}"),
+            )
+            .into()
+        } else {
+            PuzzleError::from(err.to_string())
+        }
     }
 
     /// Load input for given `year` and `day` into a `String`
+    ///
+    /// Any response with a non-success status code (e.g., when the session cookie is expired)
+    /// results in an error including the status code and the first line of the response body.
+    /// If the puzzle is not yet unlocked, the error is of kind [`Kind::Locked`]. A response body
+    /// which is not valid UTF-8 results in an error naming the offset of the first invalid byte.
+    ///
+    /// Inputs are requested with `Accept-Encoding: identity`. Compressed downloads are not
+    /// supported, since the `gzip` and `deflate` features of `reqwest` are not enabled.
+    ///
+    /// Transient failures are retried as configured with [`PuzzleIO::with_retries`].
     pub fn load_input(&self, year: u16, day: u16) -> Result<String, PuzzleError> {
-        self.request_builder(false, year, day, "input")
+        self.get(year, day, "input")
+    }
+
+    /// Load the puzzle description for given `year` and `day`
+    ///
+    /// The contents of all `<article class="day-desc">` elements are converted to Markdown with
+    /// HTML entities decoded, see [`html_to_markdown`], and separated by empty lines. The
+    /// description of part 2 is only contained once part 1 is solved.
+    ///
+    /// Errors are handled as in [`PuzzleIO::load_input`].
+    pub fn load_prompt(&self, year: u16, day: u16) -> Result<String, PuzzleError> {
+        let text = self.get(year, day, "")?;
+        let articles = extract_articles(&text);
+        if articles.is_empty() {
+            return Err(PuzzleError::from(format!(
+                "No puzzle description found for {year}/{day}"
+            )));
+        }
+
+        Ok(articles
+            .into_iter()
+            .map(html_to_markdown)
+            .collect::<Vec<_>>()
+            .join("\n\n"))
+    }
+
+    /// Check whether the session cookie is currently valid
+    ///
+    /// A single page is requested with the session cookie, which is cheaper than downloading an
+    /// input and avoids writing an error page instead of an input if the session is expired.
+    ///
+    /// Returns `Ok(false)` if the server does not accept the session, i.e., it serves the page
+    /// for anonymous users or rejects the request with status 400, 401, or 403. Network errors,
+    /// other error statuses, e.g., 429 if rate limited or 404 for a wrong base URL, and
+    /// unexpected pages are errors, so that they are not mistaken for an expired session.
+    pub fn check_session(&self) -> Result<bool, PuzzleError> {
+        check_network()?;
+
+        let url = join_url(&self.base_url, ["events"])?;
+        let response = self
+            .client()?
+            .get(url.clone())
+            .header("Cookie", format!("session={}", self.session))
+            .header("User-Agent", &self.user_agent)
             .send()
-            .map_err(|err| PuzzleError::from(err.to_string()))?
+            .map_err(|err| Self::map_session_err(err, &url))?;
+        let status = response.status();
+        if matches!(
+            status,
+            StatusCode::BAD_REQUEST | StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN
+        ) {
+            return Ok(false);
+        }
+        let text = response
             .text()
-            .map_err(|err| PuzzleError::from(err.to_string()))
+            .map_err(|err| Self::map_session_err(err, &url))?;
+        if !status.is_success() {
+            return Err(PuzzleError::from(format!(
+                "Checking the session at {url} failed with status code {status}"
+            )));
+        }
+
+        if text.contains(LOGGED_IN_MARKER) {
+            Ok(true)
+        } else if text.contains(LOGGED_OUT_MARKER) {
+            Ok(false)
+        } else {
+            Err(PuzzleError::from(format!(
+                "Could not tell from {url} whether the session is valid"
+            )))
+        }
+    }
+
+    /// map errors from sending the request of [`PuzzleIO::check_session`]
+    fn map_session_err(err: reqwest::Error, url: &Url) -> PuzzleError {
+        let kind = if err.is_timeout() {
+            std::io::ErrorKind::TimedOut
+        } else {
+            std::io::ErrorKind::Other
+        };
+        std::io::Error::new(kind, format!("Could not check the session at {url}: {err}")).into()
+    }
+
+    /// get the text at `path` for given `year` and `day` with retries
+    fn get(&self, year: u16, day: u16, path: &str) -> Result<String, PuzzleError> {
+        check_network()?;
+        validate(year, day)?;
+
+        let mut attempts = 0;
+        let mut delay = self.retry_delay;
+        loop {
+            attempts += 1;
+            match self.try_get(year, day, path) {
+                Ok(text) => return Ok(text),
+                Err(Failure::Transient(_)) if attempts <= self.retries as usize => {
+                    thread::sleep(delay);
+                    delay *= 2;
+                }
+                Err(Failure::Transient(err)) | Err(Failure::Permanent(err)) => {
+                    return Err(Self::with_attempts(err, attempts))
+                }
+            }
+        }
+    }
+
+    /// single attempt to get the text at `path`
+    fn try_get(&self, year: u16, day: u16, path: &str) -> Result<String, Failure> {
+        let response = self
+            .request_builder(false, year, day, path)
+            .map_err(Failure::Permanent)?
+            .send()
+            .map_err(|err| Failure::Transient(Self::map_send_err(err, year, day)))?;
+
+        let status = response.status();
+        let bytes = response
+            .bytes()
+            .map_err(|err| Failure::Transient(Self::map_send_err(err, year, day)))?;
+
+        if !status.is_success() {
+            let text = String::from_utf8_lossy(&bytes);
+            let err = Self::status_err(year, day, status, &text);
+            return Err(if status.is_server_error() {
+                Failure::Transient(err)
+            } else {
+                Failure::Permanent(err)
+            });
+        }
+
+        Self::decode(year, day, bytes.to_vec()).map_err(Failure::Permanent)
+    }
+
+    /// decode a response body as UTF-8, invalid content is an error naming the byte offset of
+    /// the first invalid byte
+    pub(crate) fn decode(year: u16, day: u16, bytes: Vec<u8>) -> Result<String, PuzzleError> {
+        String::from_utf8(bytes).map_err(|err| {
+            PuzzleError::from(format!(
+                "Response for {year}/{day} is not valid UTF-8 at byte {}",
+                err.utf8_error().valid_up_to()
+            ))
+        })
+    }
+
+    /// create error for a response with non-success status code
+    ///
+    /// A not found response for a puzzle which is not yet unlocked results in an error of kind
+    /// [`Kind::Locked`].
+    pub(crate) fn status_err(year: u16, day: u16, status: StatusCode, text: &str) -> PuzzleError {
+        if status == StatusCode::NOT_FOUND && text.contains(LOCKED_MARKER) {
+            return PuzzleError::locked(year, day);
+        }
+
+        PuzzleError::from(format!(
+            "Request for {year}/{day} failed with status code {status}: {}",
+            text.lines().next().unwrap_or_default()
+        ))
+    }
+
+    /// add the number of attempts made to an error, preserving the kind of IO errors
+    fn with_attempts(err: PuzzleError, attempts: usize) -> PuzzleError {
+        let suffix = if attempts == 1 { "" } else { "s" };
+        match err.kind() {
+            Kind::IoError(source) => std::io::Error::new(
+                source.kind(),
+                format!("{source} (failed after {attempts} attempt{suffix})"),
+            )
+            .into(),
+            Kind::Locked(..) => err,
+            _ => PuzzleError::from(format!("{err} (failed after {attempts} attempt{suffix})")),
+        }
     }
 
     /// Submit the `result` for a given `year`, `day`, and `star`.
@@ -81,11 +579,13 @@ impl PuzzleIO {
         level: u8,
         result: &S,
     ) -> Result<SubmitResponse, PuzzleError> {
+        check_network()?;
+
         let response = self
-            .request_builder(true, year, day, "answer")
+            .request_builder(true, year, day, "answer")?
             .form(&[("level", level.to_string()), ("answer", result.to_string())])
             .send()
-            .map_err(|err| PuzzleError::from(err.to_string()))?;
+            .map_err(|err| Self::map_send_err(err, year, day))?;
 
         if response.status() != StatusCode::OK {
             return Err(PuzzleError::from(format!(
@@ -116,6 +616,326 @@ impl PuzzleIO {
         }
 
         // determine answer
+        SubmitResponse::parse(text)
+    }
+}
+
+/// Input loader for an arbitrary HTTP endpoint, e.g., a private mirror of puzzle inputs
+///
+/// The input is obtained by a `GET` request to the URL template with `{year}` and `{day}`
+/// substituted, sending the configured headers instead of the session cookie. Errors are
+/// handled as in [`PuzzleIO::load_input`], requests are not retried.
+///
+/// # Examples
+/// ```
+/// # use mr_kaffee_aoc::puzzle_io::HttpInputProvider;
+/// let provider = HttpInputProvider::new("https://aoc.example.com/{year}/{day}.txt")
+///     .with_header("Authorization", "Bearer my-token");
+/// assert_eq!("https://aoc.example.com/2022/5.txt", provider.url(2022, 5));
+/// ```
+pub struct HttpInputProvider {
+    /// URL with placeholders `{year}` and `{day}`
+    pub url_template: String,
+    /// headers sent with every request, see [`HttpInputProvider::with_header`]
+    pub headers: Vec<(String, String)>,
+    /// timeout applied to requests
+    pub timeout: Duration,
+    /// client shared by all requests, built on the first request
+    client: OnceLock<Client>,
+}
+
+impl std::fmt::Debug for HttpInputProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // do not print header values, which typically contain credentials
+        let headers = self
+            .headers
+            .iter()
+            .map(|(name, _)| format!("{name}: ..."))
+            .collect::<Vec<_>>();
+        f.debug_struct("HttpInputProvider")
+            .field("url_template", &self.url_template)
+            .field("headers", &headers)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+impl HttpInputProvider {
+    /// create a provider for the given URL template without headers other than the default
+    /// `User-Agent`, see [`PuzzleIO::default_user_agent`]
+    pub fn new<S: Into<String>>(url_template: S) -> Self {
+        Self {
+            url_template: url_template.into(),
+            headers: Vec::new(),
+            timeout: PuzzleIO::DEFAULT_TIMEOUT,
+            client: OnceLock::new(),
+        }
+    }
+
+    /// add a header sent with every request, a `User-Agent` header replaces the default
+    pub fn with_header<K: Into<String>, V: Into<String>>(mut self, name: K, value: V) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// set the timeout applied to requests
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        Self {
+            timeout,
+            client: OnceLock::new(),
+            ..self
+        }
+    }
+
+    /// get the URL for a given `year` and `day`
+    pub fn url(&self, year: u16, day: u16) -> String {
+        self.url_template
+            .replace("{year}", &year.to_string())
+            .replace("{day}", &day.to_string())
+    }
+
+    /// Load input for given `year` and `day` into a `String`
+    pub fn load_input(&self, year: u16, day: u16) -> Result<String, PuzzleError> {
+        check_network()?;
+        validate(year, day)?;
+
+        let client = match self.client.get() {
+            Some(client) => client,
+            None => {
+                let client = Client::builder()
+                    .timeout(self.timeout)
+                    .build()
+                    .map_err(|err| PuzzleError::from(err.to_string()))?;
+                self.client.get_or_init(|| client)
+            }
+        };
+
+        let url = self.url(year, day);
+        let url = Url::parse(&url).map_err(|err| {
+            PuzzleError::from(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "Invalid URL '{url}' from template '{}': {err}",
+                    self.url_template
+                ),
+            ))
+        })?;
+        let mut request = client.get(url);
+        if !self
+            .headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("User-Agent"))
+        {
+            request = request.header("User-Agent", PuzzleIO::default_user_agent());
+        }
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+
+        let response = request
+            .send()
+            .map_err(|err| PuzzleIO::map_send_err(err, year, day))?;
+        let status = response.status();
+        let bytes = response
+            .bytes()
+            .map_err(|err| PuzzleIO::map_send_err(err, year, day))?;
+
+        if !status.is_success() {
+            let text = String::from_utf8_lossy(&bytes);
+            return Err(PuzzleIO::status_err(year, day, status, &text));
+        }
+
+        PuzzleIO::decode(year, day, bytes.to_vec())
+    }
+}
+
+/// header of the description of part 2, only contained once part 1 is solved
+pub const PART_TWO_HEADER: &str = "--- Part Two ---";
+
+/// Check whether a puzzle description contains part 2, see [`PART_TWO_HEADER`]
+///
+/// Works for the HTML page as well as for the Markdown returned by [`PuzzleIO::load_prompt`].
+///
+/// # Examples
+/// ```
+/// # use mr_kaffee_aoc::puzzle_io::has_part_two;
+/// assert!(!has_part_two("<h2>--- Day 1: Calorie Counting ---</h2>"));
+/// assert!(has_part_two("## --- Day 1 ---\n\n...\n\n## --- Part Two ---\n\n..."));
+/// ```
+pub fn has_part_two(text: &str) -> bool {
+    text.contains(PART_TWO_HEADER)
+}
+
+/// extract the contents of all `<article class="day-desc">` elements
+fn extract_articles(text: &str) -> Vec<&str> {
+    const START: &str = "<article class=\"day-desc\">";
+    const END: &str = "</article>";
+
+    let mut articles = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(START) {
+        rest = &rest[start + START.len()..];
+        let end = rest.find(END).unwrap_or(rest.len());
+        articles.push(rest[..end].trim());
+        rest = &rest[end..];
+    }
+
+    articles
+}
+
+/// Convert the HTML of a puzzle description to Markdown
+///
+/// Headings, paragraphs, lists, code, and emphasis are converted, other tags are dropped while
+/// their content is kept. HTML entities are decoded with [`decode_entities`], so that the text
+/// contains literal characters. The content of `<pre>` blocks is kept verbatim in fenced code
+/// blocks.
+///
+/// # Examples
+/// ```
+/// # use mr_kaffee_aoc::puzzle_io::html_to_markdown;
+/// let html = "<h2>--- Day 1 ---</h2><p>Is <code>a &gt; b</code>? <em>Yes</em>!</p>";
+/// assert_eq!("## --- Day 1 ---\n\nIs `a > b`? *Yes*!", html_to_markdown(html));
+/// ```
+pub fn html_to_markdown(html: &str) -> String {
+    let mut markdown = String::new();
+    let mut in_pre = false;
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        let Some(len) = rest[start..].find('>') else {
+            break;
+        };
+        markdown.push_str(&decode_entities(&rest[..start]));
+        let tag = &rest[start + 1..start + len];
+        let end = start + len + 1;
+        let name = tag
+            .split(|c: char| c.is_whitespace())
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match (name.as_str(), in_pre) {
+            ("h2", _) => markdown.push_str("## "),
+            ("/h2" | "/p" | "/ul", _) => markdown.push_str("\n\n"),
+            ("pre", _) => {
+                markdown.push_str("```\n");
+                in_pre = true;
+            }
+            ("/pre", _) => {
+                if !markdown.ends_with('\n') {
+                    markdown.push('\n');
+                }
+                markdown.push_str("```\n\n");
+                in_pre = false;
+            }
+            ("code", false) | ("/code", false) => markdown.push('`'),
+            ("em", false) | ("/em", false) => markdown.push('*'),
+            ("li", _) => {
+                if !markdown.is_empty() && !markdown.ends_with('\n') {
+                    markdown.push('\n');
+                }
+                markdown.push_str("- ");
+            }
+            _ => (),
+        }
+        rest = &rest[end..];
+    }
+    markdown.push_str(&decode_entities(rest));
+
+    // no trailing whitespace and no more than one empty line in a row, except in code blocks
+    let mut lines = Vec::new();
+    let mut in_code = false;
+    for line in markdown.trim().lines() {
+        if line.starts_with("```") {
+            in_code = !in_code;
+        } else if in_code {
+            lines.push(line);
+            continue;
+        }
+        let line = line.trim_end();
+        if !(line.is_empty() && lines.last().is_some_and(|last: &&str| last.is_empty())) {
+            lines.push(line);
+        }
+    }
+    lines.join("\n")
+}
+
+/// Decode HTML entities such as `&gt;`, `&amp;`, or `&#39;` in `text`
+///
+/// Named entities used on the AoC pages and numeric entities are decoded, unknown entities are
+/// kept as they are. Inputs are plain text and must not be decoded.
+///
+/// # Examples
+/// ```
+/// # use mr_kaffee_aoc::puzzle_io::decode_entities;
+/// assert_eq!("a < b && c > \"d\"", decode_entities("a &lt; b &amp;&amp; c &gt; &quot;d&quot;"));
+/// assert_eq!("it's A", decode_entities("it&#39;s &#x41;"));
+/// assert_eq!("&unknown;", decode_entities("&unknown;"));
+/// ```
+pub fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest
+            .find(';')
+            .filter(|&end| end <= 10)
+            .map(|end| (&rest[1..end], end + 1));
+        let c = entity.and_then(|(name, _)| match name {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{a0}'),
+            _ => match name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok().and_then(char::from_u32),
+                None => name
+                    .strip_prefix('#')
+                    .and_then(|dec| dec.parse().ok())
+                    .and_then(char::from_u32),
+            },
+        });
+        match (c, entity) {
+            (Some(c), Some((_, len))) => {
+                decoded.push(c);
+                rest = &rest[len..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// result of a failed request attempt
+enum Failure {
+    /// failure which may disappear when retrying, e.g., network errors
+    Transient(PuzzleError),
+    /// failure which will not disappear when retrying, e.g., authentication errors
+    Permanent(PuzzleError),
+}
+
+/// Possible responses when submitting result (see [`PuzzleIO::submit_result``])
+#[derive(Debug, PartialEq, Eq)]
+pub enum SubmitResponse {
+    /// Response indicating that your result is correct, you earned a star!
+    Right,
+    /// Response indicating that you already solved this star
+    AlreadySolved,
+    /// Response indicating that your result is wrong
+    Wrong,
+    /// Response indicating that you have to wait for some time before you are allowed to
+    /// re-submitting a result. The value of this variant, if present, indicates how many
+    /// seconds you have to wait
+    Wait(Option<usize>),
+}
+
+impl SubmitResponse {
+    /// Interpret the text of a response to submitting a result
+    pub fn parse(text: &str) -> Result<Self, PuzzleError> {
         if text.contains("That's the right answer") {
             Ok(SubmitResponse::Right)
         } else if text.contains("Did you already complete it") {
@@ -138,28 +958,315 @@ impl PuzzleIO {
             Err(PuzzleError::from("Can't interpret answer."))
         }
     }
-}
 
-/// Possible responses when submitting result (see [`PuzzleIO::submit_result``])
-#[derive(Debug, PartialEq, Eq)]
-pub enum SubmitResponse {
-    /// Response indicating that your result is correct, you earned a star!
-    Right,
-    /// Response indicating that you already solved this star
-    AlreadySolved,
-    /// Response indicating that your result is wrong
-    Wrong,
-    /// Response indicating that you have to wait for some time before you are allowed to
-    /// re-submitting a result. The value of this variant, if present, indicates how many
-    /// seconds you have to wait
-    Wait(Option<usize>),
+    /// get the time to wait before re-submitting for [`SubmitResponse::Wait`] variants, if
+    /// known, [`Option::None`] otherwise
+    pub fn wait_duration(&self) -> Option<Duration> {
+        match self {
+            SubmitResponse::Wait(Some(s)) => Some(Duration::from_secs(*s as u64)),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
 
     #[test]
+    pub fn test_from_env() {
+        let vars = |value: &'static str| {
+            move |key: &str| (key == PuzzleIO::SESSION_ENV).then(|| value.to_string())
+        };
+
+        let result = PuzzleIO::from_env_with(|_| None);
+        assert!(
+            matches!(
+                result.as_ref().map_err(|err| err.kind()),
+                Err(Kind::IoError(err)) if err.kind() == std::io::ErrorKind::NotFound
+            ),
+            "Expected a not found error, found: {result:?}"
+        );
+
+        assert!(PuzzleIO::from_env_with(vars(" ")).is_err());
+
+        let result = PuzzleIO::from_env_with(vars(" 0123456789\n"));
+        assert_eq!("0123456789", result.unwrap().session);
+    }
+
+    #[test]
+    pub fn test_extract_articles() {
+        let text = r#"<main>
+<article class="day-desc"><h2>--- Day 1 ---</h2><p>Part 1</p></article>
+<p>Your puzzle answer was <code>42</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Part 2</p></article>
+</main>"#;
+        assert_eq!(
+            vec![
+                "<h2>--- Day 1 ---</h2><p>Part 1</p>",
+                "<h2 id=\"part2\">--- Part Two ---</h2><p>Part 2</p>"
+            ],
+            extract_articles(text)
+        );
+
+        let text = r#"<main><article class="day-desc"><p>Part 1</p></article></main>"#;
+        assert_eq!(vec!["<p>Part 1</p>"], extract_articles(text));
+
+        assert!(extract_articles("<main></main>").is_empty());
+    }
+
+    #[test]
+    pub fn test_html_to_markdown() {
+        let html = r#"<h2>--- Day 5: Supply Stacks ---</h2><p>The <em>expedition</em> can depart &amp; the crates move:</p>
+<pre><code>    [D]    
+[N] <em>[C]</em>    
+
+
+ 1   2 
+</code></pre>
+<ul>
+<li>Move &lt;n&gt; crates from <code>1</code> to <code>2</code>.</li>
+<li><a href="/2022/day/4">Day&nbsp;4</a> is &quot;done&quot;.</li>
+</ul>"#;
+        assert_eq!(
+            "## --- Day 5: Supply Stacks ---\n\n\
+            The *expedition* can depart & the crates move:\n\n\
+            ```\n    [D]    \n[N] [C]    \n\n\n 1   2 \n```\n\n\
+            - Move <n> crates from `1` to `2`.\n\
+            - Day\u{a0}4 is \"done\".",
+            html_to_markdown(html)
+        );
+    }
+
+    #[test]
+    pub fn test_url() {
+        let puzzle_io = PuzzleIO::from("my-session");
+        let url = |puzzle_io: &PuzzleIO, path| puzzle_io.url(2022, 1, path).unwrap().to_string();
+        assert_eq!(
+            "https://adventofcode.com/2022/day/1/input",
+            url(&puzzle_io, "input")
+        );
+        assert_eq!("https://adventofcode.com/2022/day/1", url(&puzzle_io, ""));
+
+        let puzzle_io = puzzle_io.with_base_url("http://localhost:8080//");
+        assert_eq!(
+            "http://localhost:8080/2022/day/1/input",
+            url(&puzzle_io, "input")
+        );
+
+        // segments are percent-encoded instead of changing the structure of the URL
+        let puzzle_io = puzzle_io.with_base_url("https://mirror.example.com/aoc/");
+        assert_eq!(
+            "https://mirror.example.com/aoc/2022/day/1/in%20put%3F%2F..",
+            url(&puzzle_io, "in put?/..")
+        );
+
+        let puzzle_io = puzzle_io.with_base_url("mirror.example.com");
+        assert!(puzzle_io.url(2022, 1, "input").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "no-network")]
+    pub fn test_no_network() {
+        let err = PuzzleIO::from("my-session")
+            .load_input(2022, 1)
+            .unwrap_err();
+        assert!(
+            matches!(err.kind(), Kind::IoError(source) if source.kind() == std::io::ErrorKind::Unsupported)
+        );
+        assert!(err.to_string().contains("network disabled in test builds"));
+    }
+
+    /// serve a single request on a local port with a `200 OK` response with given `body`,
+    /// return the base URL and a handle to obtain the request lines
+    #[cfg(not(feature = "no-network"))]
+    pub(crate) fn serve_once(body: &'static [u8]) -> (String, thread::JoinHandle<Vec<String>>) {
+        serve_once_with_status("200 OK", body)
+    }
+
+    /// serve a single request on a local port with a response with given `status` line and
+    /// `body`, see [`serve_once`]
+    #[cfg(not(feature = "no-network"))]
+    fn serve_once_with_status(
+        status: &'static str,
+        body: &'static [u8],
+    ) -> (String, thread::JoinHandle<Vec<String>>) {
+        use std::{
+            io::{BufRead, BufReader, Write},
+            net::TcpListener,
+        };
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let request = BufReader::new(&stream)
+                .lines()
+                .map(Result::unwrap)
+                .take_while(|line| !line.is_empty())
+                .collect::<Vec<_>>();
+            let header = format!(
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            stream.write_all(header.as_bytes()).unwrap();
+            stream.write_all(body).unwrap();
+            request
+        });
+
+        (base_url, server)
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-network"))]
+    pub fn test_load_input_invalid_utf8() {
+        let (base_url, server) = serve_once(b"12\n\xff34\n");
+
+        let puzzle_io = PuzzleIO::from("my-session").with_base_url(base_url);
+        let err = puzzle_io.load_input(2022, 1).unwrap_err();
+        assert!(
+            err.to_string().contains("not valid UTF-8 at byte 3"),
+            "{err}"
+        );
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-network"))]
+    pub fn test_check_session() {
+        for (body, valid) in [
+            (&b"<a href=\"/2022/auth/logout\">[Log Out]</a>"[..], true),
+            (&b"<a href=\"/auth/login\">[Log In]</a>"[..], false),
+        ] {
+            let (base_url, server) = serve_once(body);
+            let puzzle_io = PuzzleIO::from("my-session").with_base_url(base_url);
+            assert_eq!(valid, puzzle_io.check_session().unwrap());
+
+            let request = server.join().unwrap();
+            assert_eq!("GET /events HTTP/1.1", request[0]);
+        }
+
+        let (base_url, server) = serve_once(b"something else");
+        let puzzle_io = PuzzleIO::from("my-session").with_base_url(base_url);
+        assert!(puzzle_io.check_session().is_err());
+        server.join().unwrap();
+
+        for (status, valid) in [
+            ("403 Forbidden", Some(false)),
+            ("429 Too Many Requests", None),
+        ] {
+            let (base_url, server) = serve_once_with_status(status, b"");
+            let puzzle_io = PuzzleIO::from("my-session").with_base_url(base_url);
+            match (puzzle_io.check_session(), valid) {
+                (Ok(result), Some(valid)) => assert_eq!(valid, result),
+                (Err(err), None) => assert!(err.to_string().contains("429"), "{err}"),
+                (result, _) => panic!("Unexpected result for {status}: {result:?}"),
+            }
+            server.join().unwrap();
+        }
+
+        // nothing listens on the port anymore
+        let err = puzzle_io.check_session().unwrap_err();
+        assert!(matches!(err.kind(), Kind::IoError(_)), "{err}");
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-network"))]
+    pub fn test_http_input_provider() {
+        let (base_url, server) = serve_once(b"mirrored\n");
+
+        let provider = HttpInputProvider::new(format!("{base_url}inputs/{{year}}-{{day}}"))
+            .with_header("Authorization", "Bearer my-token");
+        assert_eq!("mirrored\n", provider.load_input(2022, 7).unwrap());
+        assert!(!format!("{provider:?}").contains("my-token"));
+
+        let request = server.join().unwrap();
+        assert_eq!("GET /inputs/2022-7 HTTP/1.1", request[0]);
+        assert!(request
+            .iter()
+            .any(|line| line.eq_ignore_ascii_case("authorization: Bearer my-token")));
+        assert!(!request
+            .iter()
+            .any(|line| line.to_lowercase().starts_with("cookie:")));
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-network"))]
+    pub fn test_load_input_local_server() {
+        let (base_url, server) = serve_once(b"input\n");
+
+        let puzzle_io = PuzzleIO::from("my-session").with_base_url(base_url);
+        assert_eq!("input\n", puzzle_io.load_input(2022, 1).unwrap());
+
+        let request = server.join().unwrap();
+        assert_eq!("GET /2022/day/1/input HTTP/1.1", request[0]);
+        assert!(request
+            .iter()
+            .any(|line| line.eq_ignore_ascii_case("cookie: session=my-session")));
+        assert!(request.iter().any(|line| line
+            .to_lowercase()
+            .starts_with("user-agent: mr-kaffee-aoc/")));
+        assert!(request
+            .iter()
+            .any(|line| line.eq_ignore_ascii_case("accept-encoding: identity")));
+    }
+
+    #[test]
+    pub fn test_client_reused() {
+        let puzzle_io = PuzzleIO::from("my-session");
+        let client = puzzle_io.client().unwrap() as *const Client;
+        assert!(std::ptr::eq(client, puzzle_io.client().unwrap()));
+
+        // changing the timeout requires a new client
+        let puzzle_io = puzzle_io.with_timeout(Duration::from_secs(5));
+        assert!(puzzle_io.client.get().is_none());
+    }
+
+    #[test]
+    pub fn test_status_err_locked() {
+        let text = "Please don't repeatedly request this endpoint before it unlocks! The calendar \
+            countdown is synchronized with the server time; the link will be enabled on the \
+            calendar the instant this puzzle becomes available.";
+        let err = PuzzleIO::status_err(2022, 25, StatusCode::NOT_FOUND, text);
+        assert!(matches!(err.kind(), Kind::Locked(2022, 25)));
+        assert!(matches!(
+            PuzzleIO::with_attempts(err, 1).kind(),
+            Kind::Locked(2022, 25)
+        ));
+
+        let err = PuzzleIO::status_err(2022, 25, StatusCode::BAD_REQUEST, "Puzzle inputs differ");
+        assert!(matches!(err.kind(), Kind::Other));
+    }
+
+    #[test]
+    pub fn test_submit_response_parse() {
+        let response = SubmitResponse::parse(
+            "<p>That's the right answer! You are one gold star closer to saving your vacation.</p>",
+        );
+        assert_eq!(SubmitResponse::Right, response.unwrap());
+
+        let response =
+            SubmitResponse::parse("<p>That's not the right answer; your answer is too high.</p>");
+        assert_eq!(SubmitResponse::Wrong, response.unwrap());
+
+        let response = SubmitResponse::parse(
+            "<p>You don't seem to be solving the right level.  Did you already complete it?</p>",
+        );
+        assert_eq!(SubmitResponse::AlreadySolved, response.unwrap());
+
+        let response = SubmitResponse::parse(
+            "<p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 20s left to wait.</p>",
+        )
+        .unwrap();
+        assert_eq!(SubmitResponse::Wait(Some(80)), response);
+        assert_eq!(Some(Duration::from_secs(80)), response.wait_duration());
+
+        assert!(SubmitResponse::parse("<p>Something else</p>").is_err());
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-network"))]
     pub fn test_submit() {
         let puzzle_io = PuzzleIO::from("53616c7465645f5f26a828ab5a2977e2f4893e0ab7aeab2f520b1c62f6db37c4b6425bb1626c7b38342a19cc02acbd686204588c82e03b0bcb202faf54e96241");
 
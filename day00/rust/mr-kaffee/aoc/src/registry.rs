@@ -0,0 +1,182 @@
+//! Module to collect puzzles and run them together
+//!
+//! Every day registers its `puzzle()` explicitly, days whose crates are not compiled in are
+//! simply not part of the registry.
+//!
+//! A day opts in by invoking [`register_puzzle!`](crate::register_puzzle) in its library crate,
+//! which adds a function `register` feeding its puzzle into a [`PuzzleRegistry`]. A runner then
+//! calls `register` of every day crate it depends on. Crates which do not invoke the macro have
+//! no such function and are not affected. Collecting the days without any list in the runner
+//! needs link-time registration, e.g., with the `inventory` crate, which is not used here.
+use crate::{summary::RunSummary, GenericPuzzle};
+
+/// Add a function `pub fn register(registry: &mut PuzzleRegistry)` to the invoking crate, which
+/// registers the puzzle returned by the function `$puzzle`, see [`PuzzleRegistry::register`]
+///
+/// # Examples
+/// ```
+/// # use mr_kaffee_aoc::{register_puzzle, registry::PuzzleRegistry, Puzzle, Star};
+/// fn puzzle() -> Puzzle<'static, &'static str, usize, usize, usize, usize> {
+///     Puzzle {
+///         year: 2022,
+///         day: 1,
+///         input: "",
+///         star1: Some(Star { name: "Star 1", f: &|_| 1, exp: None }),
+///         star2: None,
+///     }
+/// }
+///
+/// register_puzzle!(puzzle);
+///
+/// let mut registry = PuzzleRegistry::new();
+/// register(&mut registry);
+/// assert_eq!(1, registry.puzzles(2022).count());
+/// ```
+#[macro_export]
+macro_rules! register_puzzle {
+    ($puzzle:path) => {
+        /// register the puzzle of this crate with `registry`
+        pub fn register(registry: &mut $crate::registry::PuzzleRegistry) {
+            registry.register($puzzle());
+        }
+    };
+}
+
+/// Collection of puzzles, possibly for several years
+#[derive(Default)]
+pub struct PuzzleRegistry {
+    puzzles: Vec<Box<dyn GenericPuzzle>>,
+}
+
+impl PuzzleRegistry {
+    /// create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// register a puzzle
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_aoc::{Puzzle, Star, registry::PuzzleRegistry};
+    /// let mut registry = PuzzleRegistry::new();
+    /// registry.register(Puzzle {
+    ///     year: 2022,
+    ///     day: 1,
+    ///     input: "",
+    ///     star1: Some(Star { name: "Star 1", f: &|_: &&str| 1, exp: None }),
+    ///     star2: None::<Star<&str, usize, usize>>,
+    /// });
+    /// assert_eq!(1, registry.puzzles(2022).count());
+    /// ```
+    pub fn register<P: GenericPuzzle + 'static>(&mut self, puzzle: P) -> &mut Self {
+        self.puzzles.push(Box::new(puzzle));
+        self
+    }
+
+    /// iterate over the puzzles registered for `year` in day order
+    pub fn puzzles(&self, year: u16) -> impl Iterator<Item = &dyn GenericPuzzle> {
+        let mut puzzles = self
+            .puzzles
+            .iter()
+            .map(|puzzle| puzzle.as_ref())
+            .filter(|puzzle| puzzle.year() == year)
+            .collect::<Vec<_>>();
+        puzzles.sort_by_key(|puzzle| puzzle.day());
+        puzzles.into_iter()
+    }
+
+    /// solve all puzzles registered for `year` in day order without printing and collect the
+    /// results into a [`RunSummary`]
+    pub fn run_summary(&self, year: u16) -> RunSummary {
+        let mut summary = RunSummary::default();
+        for puzzle in self.puzzles(year) {
+            puzzle.solve_summary(&mut summary);
+        }
+        summary
+    }
+
+    /// solve all puzzles registered for `year` in day order, print a summary table of results
+    /// and timings, and return the number of failed stars
+    pub fn run_all(&self, year: u16) -> usize {
+        let summary = self.run_summary(year);
+        println!("\nSummary for {year}\n{summary}");
+        summary.failed()
+    }
+}
+
+impl FromIterator<Box<dyn GenericPuzzle>> for PuzzleRegistry {
+    fn from_iter<I: IntoIterator<Item = Box<dyn GenericPuzzle>>>(iter: I) -> Self {
+        Self {
+            puzzles: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{summary::Status, Puzzle, Star};
+
+    fn puzzle(day: u16, fail: bool) -> Puzzle<'static, &'static str, usize, usize, usize, usize> {
+        Puzzle {
+            year: 2022,
+            day,
+            input: "",
+            star1: Some(Star {
+                name: "Star 1",
+                f: &|_| 1,
+                exp: Some(1),
+            }),
+            star2: Some(Star {
+                name: "Star 2",
+                f: if fail { &|_| 3 } else { &|_| 2 },
+                exp: Some(2),
+            }),
+        }
+    }
+
+    #[test]
+    pub fn test_run_all() {
+        let mut registry = PuzzleRegistry::new();
+        registry
+            .register(puzzle(3, false))
+            .register(puzzle(1, true))
+            .register(puzzle(2, false));
+
+        assert_eq!(
+            vec![1, 2, 3],
+            registry
+                .puzzles(2022)
+                .map(|puzzle| puzzle.day())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(0, registry.puzzles(2021).count());
+        assert_eq!(1, registry.run_all(2022));
+        assert_eq!(0, registry.run_all(2021));
+
+        let summary = registry.run_summary(2022);
+        assert_eq!(6, summary.rows.len());
+        assert_eq!((5, 1), (summary.passed(), summary.failed()));
+        assert_eq!(
+            (1, 2, Status::Failed),
+            (
+                summary.rows[1].day,
+                summary.rows[1].star,
+                summary.rows[1].status
+            )
+        );
+
+        let table = summary.to_string();
+        assert!(table.contains("5 passed, 1 failed"));
+        let columns = table
+            .lines()
+            .filter(|line| !line.starts_with('-'))
+            .map(|line| line.match_indices('|').map(|(idx, _)| idx).collect())
+            .collect::<Vec<Vec<_>>>();
+        assert!(
+            columns.iter().all(|c| c == &columns[0]),
+            "table not aligned:\n{table}"
+        );
+    }
+}
@@ -0,0 +1,333 @@
+//! Module to collect results of several puzzles into a tabular summary
+use crate::err::PuzzleError;
+use std::{
+    fmt,
+    fs::OpenOptions,
+    io::Write,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// default path of the file [`RunSummary::append_csv`] appends answers to
+pub const DEFAULT_RESULTS_PATH: &str = "results.csv";
+
+/// header line of the file written by [`RunSummary::append_csv`]
+pub const RESULTS_HEADER: &str = "year,day,star,answer,timestamp";
+
+/// Status of a solved star
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// the result matches the expected result
+    Passed,
+    /// the result does not match the expected result
+    Failed,
+    /// there is no expected result to verify the result against
+    Unverified,
+    /// an error occurred while solving the star
+    Error,
+}
+
+impl Status {
+    /// `true` for [`Status::Failed`] and [`Status::Error`]
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::Failed | Self::Error)
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // pad, so that width and alignment given in the format string apply
+        f.pad(match self {
+            Self::Passed => "OK",
+            Self::Failed => "FAILED",
+            Self::Unverified => "UNVERIFIED",
+            Self::Error => "ERROR",
+        })
+    }
+}
+
+/// Row of a [`RunSummary`] for a single star
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SummaryRow {
+    /// year of the puzzle
+    pub year: u16,
+    /// day of the puzzle
+    pub day: u16,
+    /// the star, `1` or `2`
+    pub star: u8,
+    /// the result formatted into a `String` or the error message
+    pub result: String,
+    /// the expected result formatted into a `String`, if any
+    pub expected: Option<String>,
+    /// time it took to solve the star
+    pub duration: Duration,
+    /// the status
+    pub status: Status,
+}
+
+/// Summary of a run of several puzzles, rendered as an aligned table with totals by its
+/// [`fmt::Display`] implementation
+///
+/// # Examples
+/// ```
+/// # use mr_kaffee_aoc::summary::{RunSummary, Status, SummaryRow};
+/// # use std::time::Duration;
+/// let mut summary = RunSummary::default();
+/// summary.push(SummaryRow {
+///     year: 2022,
+///     day: 1,
+///     star: 1,
+///     result: "24000".to_string(),
+///     expected: Some("24000".to_string()),
+///     duration: Duration::from_millis(1),
+///     status: Status::Passed,
+/// });
+/// assert_eq!(1, summary.passed());
+/// assert_eq!(0, summary.failed());
+/// assert!(summary.to_string().contains("24000"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunSummary {
+    /// the rows in the order they were added
+    pub rows: Vec<SummaryRow>,
+}
+
+impl RunSummary {
+    /// add a row
+    pub fn push(&mut self, row: SummaryRow) {
+        self.rows.push(row);
+    }
+
+    /// number of rows which are not failures, see [`Status::is_failure`]
+    pub fn passed(&self) -> usize {
+        self.rows.len() - self.failed()
+    }
+
+    /// number of rows which are failures, see [`Status::is_failure`]
+    pub fn failed(&self) -> usize {
+        self.rows
+            .iter()
+            .filter(|row| row.status.is_failure())
+            .count()
+    }
+
+    /// total time of all rows
+    pub fn total_duration(&self) -> Duration {
+        self.rows.iter().map(|row| row.duration).sum()
+    }
+
+    /// render the answers as CSV lines `year,day,star,answer,timestamp` with `timestamp` in
+    /// seconds since the Unix epoch, rows with [`Status::Error`] are skipped
+    ///
+    /// Answers containing commas, quotes, or line breaks, e.g., letters drawn on a grid, are
+    /// quoted.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_aoc::summary::{RunSummary, Status, SummaryRow};
+    /// # use std::time::Duration;
+    /// let row = SummaryRow {
+    ///     year: 2022,
+    ///     day: 10,
+    ///     star: 2,
+    ///     result: "a \"b\", c".to_string(),
+    ///     expected: None,
+    ///     duration: Duration::from_millis(1),
+    ///     status: Status::Unverified,
+    /// };
+    /// let failed = SummaryRow { star: 1, status: Status::Error, ..row.clone() };
+    /// let summary = RunSummary { rows: vec![failed, row] };
+    /// assert_eq!("2022,10,2,\"a \"\"b\"\", c\",1670000000\n", summary.to_csv(1_670_000_000));
+    /// ```
+    pub fn to_csv(&self, timestamp: u64) -> String {
+        self.rows
+            .iter()
+            .filter(|row| row.status != Status::Error)
+            .map(|row| {
+                let answer = if row.result.contains([',', '"', '\n', '\r']) {
+                    format!("\"{}\"", row.result.replace('"', "\"\""))
+                } else {
+                    row.result.clone()
+                };
+                format!(
+                    "{},{},{},{answer},{timestamp}\n",
+                    row.year, row.day, row.star
+                )
+            })
+            .collect()
+    }
+
+    /// append the answers to the CSV file at `path` with the current time, see
+    /// [`RunSummary::to_csv`]
+    ///
+    /// The file is created with the header [`RESULTS_HEADER`] if it does not exist. Only the run
+    /// which actually creates the file writes the header, so that concurrent first runs do not
+    /// write it twice. All lines are appended with a single write, so that concurrent runs do
+    /// not interleave lines.
+    pub fn append_csv<P: AsRef<Path>>(&self, path: P) -> Result<(), PuzzleError> {
+        let path = path.as_ref();
+        let context = || format!("Failed to append results to {}", path.to_string_lossy());
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        match OpenOptions::new().write(true).create_new(true).open(path) {
+            Ok(mut file) => file
+                .write_all(format!("{RESULTS_HEADER}\n").as_bytes())
+                .map_err(|err| PuzzleError::from(err).with_context(context()))?,
+            Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => (),
+            Err(err) => return Err(PuzzleError::from(err).with_context(context())),
+        }
+
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(path)
+            .map_err(|err| PuzzleError::from(err).with_context(context()))?;
+        let content = self.to_csv(timestamp);
+        file.write_all(content.as_bytes())
+            .and_then(|_| file.flush())
+            .map_err(|err| PuzzleError::from(err).with_context(context()))
+    }
+}
+
+impl fmt::Display for RunSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let durations = self
+            .rows
+            .iter()
+            .map(|row| format!("{:?}", row.duration))
+            .collect::<Vec<_>>();
+        let total = format!("{:?}", self.total_duration());
+        let totals = format!("{} passed, {} failed", self.passed(), self.failed());
+        let results = self
+            .rows
+            .iter()
+            .map(|row| cell(&row.result))
+            .collect::<Vec<_>>();
+        let expected = self
+            .rows
+            .iter()
+            .map(|row| row.expected.as_deref().map_or("-".to_string(), cell))
+            .collect::<Vec<_>>();
+
+        // padding counts characters, e.g., the 'µ' in durations is a single one
+        let width = |header: &str, values: &mut dyn Iterator<Item = &String>| {
+            values
+                .map(|value| value.chars().count())
+                .fold(header.len(), usize::max)
+        };
+        let w_result = width("Result", &mut results.iter().chain([&totals]));
+        let w_expected = width("Expected", &mut expected.iter());
+        let w_duration = width("Time", &mut durations.iter().chain([&total]));
+        let separator = format!(
+            "-----+-----+------+-{:-<w_result$}-+-{:-<w_expected$}-+-{:-<w_duration$}-+-{:-<10}",
+            "", "", "", ""
+        );
+
+        writeln!(
+            f,
+            "Year | Day | Star | {:<w_result$} | {:<w_expected$} | {:>w_duration$} | Status",
+            "Result", "Expected", "Time"
+        )?;
+        writeln!(f, "{separator}")?;
+        for (((row, result), expected), duration) in self
+            .rows
+            .iter()
+            .zip(&results)
+            .zip(&expected)
+            .zip(&durations)
+        {
+            writeln!(
+                f,
+                "{:>4} | {:>3} | {:>4} | {:<w_result$} | {:<w_expected$} | {:>w_duration$} | {}",
+                row.year, row.day, row.star, result, expected, duration, row.status
+            )?;
+        }
+        writeln!(f, "{separator}")?;
+        write!(
+            f,
+            "{:>4} | {:>3} | {:>4} | {:<w_result$} | {:<w_expected$} | {:>w_duration$} |",
+            "", "", "", totals, "", total
+        )
+    }
+}
+
+/// render a value for a single table cell, line breaks, e.g., of letters drawn on a grid, are
+/// escaped as `\n`
+fn cell(value: &str) -> String {
+    value.replace("\r\n", "\n").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{self, remove_dir_all};
+
+    fn summary(day: u16, result: &str) -> RunSummary {
+        RunSummary {
+            rows: vec![SummaryRow {
+                year: 2022,
+                day,
+                star: 1,
+                result: result.to_string(),
+                expected: None,
+                duration: Duration::from_millis(1),
+                status: Status::Unverified,
+            }],
+        }
+    }
+
+    #[test]
+    pub fn test_display_multi_line() {
+        let mut summary = summary(10, "#..#\n####\n");
+        summary.rows.push(SummaryRow {
+            star: 2,
+            result: "äöü".to_string(),
+            duration: Duration::from_micros(5),
+            ..summary.rows[0].clone()
+        });
+
+        let table = summary.to_string();
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(6, lines.len(), "{table}");
+        assert!(lines[2].contains("| #..#\\n####\\n "), "{table}");
+        // all separators of the rows are aligned
+        let columns = |line: &str| {
+            line.chars()
+                .enumerate()
+                .filter(|(_, c)| *c == '|')
+                .map(|(pos, _)| pos)
+                .collect::<Vec<_>>()
+        };
+        for line in &lines[2..4] {
+            assert_eq!(columns(lines[0]), columns(line), "{table}");
+        }
+    }
+
+    #[test]
+    pub fn test_append_csv() {
+        let target_path = Path::new("target/test_append_csv");
+        let _ = remove_dir_all(target_path);
+        fs::create_dir_all(target_path).unwrap();
+        let path = target_path.join(DEFAULT_RESULTS_PATH);
+
+        summary(1, "24000").append_csv(&path).unwrap();
+        summary(2, "15").append_csv(&path).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        let lines = content.lines().collect::<Vec<_>>();
+        assert_eq!(3, lines.len(), "{content}");
+        assert_eq!(RESULTS_HEADER, lines[0]);
+        assert!(lines[1].starts_with("2022,1,1,24000,"), "{content}");
+        assert!(lines[2].starts_with("2022,2,1,15,"), "{content}");
+
+        // only the run creating the file writes the header, an existing file never gets one
+        fs::write(&path, "").unwrap();
+        summary(3, "7").append_csv(&path).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("2022,3,1,7,"), "{content}");
+
+        let _ = remove_dir_all(target_path);
+    }
+}
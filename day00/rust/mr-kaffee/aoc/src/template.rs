@@ -1,7 +1,569 @@
-use crate::{err::PuzzleError, puzzle_io::PuzzleIO};
+//! Module to create new puzzle crates from templates
+//!
+//! Progress is reported through the [`log`] facade: files written at level info, problems
+//! such as a suspiciously short input at level warn. No logger implementation is included,
+//! install one in the application to see the messages.
+use crate::{
+    err::{Kind, PuzzleError},
+    puzzle_io::{has_part_two, validate, HttpInputProvider, PuzzleIO},
+};
+use log::{info, warn};
 use regex::Regex;
-use std::{collections::HashMap, fs, path::Path};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{BufRead, BufReader, Cursor, IsTerminal, Read},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    process::Command,
+    str::FromStr,
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use toml_edit::{value, Array, DocumentMut, InlineTable, Item, Value};
 
+/// Line endings of files created from templates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineEnding {
+    /// `\n`
+    Lf,
+    /// `\r\n`
+    CrLf,
+    /// `\r\n` on Windows, `\n` otherwise
+    #[default]
+    Native,
+}
+
+impl LineEnding {
+    /// convert all line endings in `content`
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_aoc::template::LineEnding;
+    /// assert_eq!("a\r\nb\r\n", LineEnding::CrLf.apply("a\nb\r\n"));
+    /// assert_eq!("a\nb\n", LineEnding::Lf.apply("a\nb\r\n"));
+    /// ```
+    pub fn apply(&self, content: &str) -> String {
+        let content = content.replace("\r\n", "\n");
+        match self {
+            Self::CrLf => content.replace('\n', "\r\n"),
+            Self::Native if cfg!(windows) => content.replace('\n', "\r\n"),
+            _ => content,
+        }
+    }
+}
+
+/// Source of the dependency on this crate in a generated `Cargo.toml`, rendered into the
+/// template variable `{AOC_DEPENDENCY}`
+///
+/// # Examples
+/// ```
+/// # use mr_kaffee_aoc::template::DependencySpec;
+/// assert_eq!(
+///     r#"{ path = "../aoc" }"#,
+///     DependencySpec::Path("../aoc".into()).to_toml()
+/// );
+/// assert_eq!(r#""0.2""#, DependencySpec::Version("0.2".to_string()).to_toml());
+/// let git = DependencySpec::Git {
+///     url: "https://github.com/mr-kaffee/aoc-2022".to_string(),
+///     rev: Some("abc123".to_string()),
+/// };
+/// assert_eq!(
+///     r#"{ git = "https://github.com/mr-kaffee/aoc-2022", rev = "abc123" }"#,
+///     git.to_toml()
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DependencySpec {
+    /// local path, converted to a string with `/` as separator
+    Path(PathBuf),
+    /// published version requirement, e.g., `"0.2"`
+    Version(String),
+    /// git repository with an optional revision
+    Git { url: String, rev: Option<String> },
+}
+
+impl DependencySpec {
+    /// render the dependency as a value of an entry in the `dependencies` table
+    pub fn to_toml(&self) -> String {
+        let mut table = InlineTable::new();
+        match self {
+            Self::Path(path) => {
+                table.insert("path", slash_path(path).into());
+            }
+            Self::Version(version) => return Value::from(version.as_str()).to_string(),
+            Self::Git { url, rev } => {
+                table.insert("git", url.as_str().into());
+                if let Some(rev) = rev {
+                    table.insert("rev", rev.as_str().into());
+                }
+            }
+        }
+        table.fmt();
+        table.to_string()
+    }
+}
+
+/// Command run by [`write_files_with`] in the target directory after a crate was generated,
+/// e.g., to `git add` the new files or open an editor
+///
+/// The command gets the environment variables `AOC_YEAR` and `AOC_DAY`.
+///
+/// # Examples
+/// ```
+/// # use mr_kaffee_aoc::template::PostHook;
+/// let hook = PostHook::new("git").with_args(["add", "."]);
+/// assert_eq!("git add .", hook.to_string());
+/// assert_eq!("sh", PostHook::shell("git add .").program);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PostHook {
+    /// the program to run
+    pub program: String,
+    /// the arguments passed to the program
+    pub args: Vec<String>,
+}
+
+impl PostHook {
+    /// Create a hook running `program` without arguments
+    pub fn new<S: Into<String>>(program: S) -> Self {
+        Self {
+            program: program.into(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Create a hook running `command` with `sh -c`, or `cmd /C` on Windows
+    pub fn shell<S: Into<String>>(command: S) -> Self {
+        let (program, flag) = if cfg!(windows) {
+            ("cmd", "/C")
+        } else {
+            ("sh", "-c")
+        };
+        Self::new(program).with_args([flag.to_string(), command.into()])
+    }
+
+    /// Set the arguments
+    pub fn with_args<I, S>(self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            args: args.into_iter().map(Into::into).collect(),
+            ..self
+        }
+    }
+
+    /// Run the hook in the directory at `path` for `day` of `year`
+    ///
+    /// The output is captured and logged. If the command cannot be started or does not exit
+    /// successfully, the error contains its output.
+    pub fn run<P: AsRef<Path>>(&self, path: P, year: u16, day: u16) -> Result<(), PuzzleError> {
+        let path = path.as_ref();
+        info!(
+            "Running post hook '{self}' in {} ...",
+            path.to_string_lossy()
+        );
+
+        let output = Command::new(&self.program)
+            .args(&self.args)
+            .current_dir(path)
+            .env("AOC_YEAR", year.to_string())
+            .env("AOC_DAY", day.to_string())
+            .output()
+            .map_err(|err| {
+                PuzzleError::from(err).with_context(format!("Failed to run post hook '{self}'"))
+            })?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !output.status.success() {
+            return Err(PuzzleError::from(format!(
+                "Post hook '{self}' failed with {} in '{}':\n{stdout}{stderr}",
+                output.status,
+                path.to_string_lossy()
+            )));
+        }
+
+        for line in stdout.lines().chain(stderr.lines()) {
+            info!("   {line}");
+        }
+        info!("-> OK");
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for PostHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.program)?;
+        for arg in &self.args {
+            write!(f, " {arg}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Options for [`write_files_with`]
+///
+/// # Examples
+/// ```
+/// # use mr_kaffee_aoc::template::WriteOptions;
+/// let opts = WriteOptions::new("day01").with_force(true).with_dry_run(true);
+/// assert!(opts.force);
+/// assert!(!opts.refresh_input);
+/// ```
+#[derive(Debug, Clone)]
+pub struct WriteOptions {
+    /// the target directory
+    pub path: PathBuf,
+    /// the templates to create files from
+    pub templates: TemplateSet,
+    /// JSON object with additional template variables
+    pub config: String,
+    /// overwrite files in an existing target directory
+    pub force: bool,
+    /// only log the files that would be written
+    pub dry_run: bool,
+    /// replace an existing `input.txt`
+    pub refresh_input: bool,
+    /// replace an existing `README.md` without part 2 in `ensure` mode
+    pub refresh_prompt: bool,
+    /// create a criterion benchmark
+    pub with_benches: bool,
+    /// workspace root to scan for packages with the same name as the new crate
+    pub workspace_root: Option<PathBuf>,
+    /// result types of star 1 and star 2, override `{RESULT1_TYPE}` and `{RESULT2_TYPE}`
+    pub result_types: Option<(String, String)>,
+    /// Rust edition of the generated crate, overrides `{EDITION}`, see [`EDITIONS`]
+    pub edition: Option<String>,
+    /// minimum supported Rust version added as `rust-version` to the generated `Cargo.toml`
+    pub rust_version: Option<String>,
+    /// expected results of star 1 and star 2 for `example.txt`, create an integration test
+    /// `tests/example.rs` checking them if set
+    pub example_test: Option<(String, String)>,
+    /// check that the generated crate compiles, see [`verify_generated`]
+    pub verify: bool,
+    /// keep a backup of every file replaced with different content
+    pub backup: bool,
+    /// expected result of star 1, overrides `{EXP1}`
+    pub exp1: Option<String>,
+    /// expected result of star 2, overrides `{EXP2}`
+    pub exp2: Option<String>,
+    /// line endings of files created from templates, inputs are written unchanged
+    pub line_ending: LineEnding,
+    /// path to this crate, overrides `{LIB_DIR}`, see [`WriteOptions::with_lib_path`]
+    pub lib_path: Option<PathBuf>,
+    /// only write files which do not exist
+    pub ensure: bool,
+    /// do not add `input.txt` and `example.txt` to `.gitignore`, overrides `{IGNORED_INPUTS}`
+    pub commit_inputs: bool,
+    /// fail instead of warning if the input is shorter than [`MIN_INPUT_LEN`] or looks like an
+    /// error page, see [`INPUT_ERROR_MARKERS`]
+    pub strict_input: bool,
+    /// derive `Serialize` for the parsed input behind a feature `serde` of the generated crate,
+    /// overrides `{SERDE_DERIVE}`
+    pub serde: bool,
+    /// format the generated Rust files with `rustfmt`
+    pub format: bool,
+    /// source of the dependency on this crate, overrides `{AOC_DEPENDENCY}`, defaults to the
+    /// path `{LIB_DIR}`
+    pub dependency: Option<DependencySpec>,
+    /// path to a shared utility crate relative to the target directory, added to the
+    /// dependencies as [`UTIL_CRATE_NAME`], see [`scaffold_util_crate`]
+    pub util_crate: Option<PathBuf>,
+    /// command run in the target directory after the files were written, see [`PostHook`]
+    pub post_hook: Option<PostHook>,
+}
+
+impl WriteOptions {
+    /// Create options for the target directory at `path` with the built-in templates, no
+    /// additional variables and all flags unset
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            templates: TemplateSet::default(),
+            config: "{}".to_string(),
+            force: false,
+            dry_run: false,
+            refresh_input: false,
+            refresh_prompt: false,
+            with_benches: false,
+            workspace_root: None,
+            result_types: None,
+            edition: None,
+            rust_version: None,
+            example_test: None,
+            verify: false,
+            backup: false,
+            exp1: None,
+            exp2: None,
+            line_ending: LineEnding::default(),
+            lib_path: None,
+            ensure: false,
+            commit_inputs: false,
+            strict_input: false,
+            serde: false,
+            format: false,
+            dependency: None,
+            util_crate: None,
+            post_hook: None,
+        }
+    }
+
+    /// Set the templates
+    pub fn with_template_set(self, templates: TemplateSet) -> Self {
+        Self { templates, ..self }
+    }
+
+    /// Set the JSON object with additional template variables
+    pub fn with_config<S: Into<String>>(self, config: S) -> Self {
+        Self {
+            config: config.into(),
+            ..self
+        }
+    }
+
+    /// Set the force flag
+    pub fn with_force(self, force: bool) -> Self {
+        Self { force, ..self }
+    }
+
+    /// Set the dry run flag
+    pub fn with_dry_run(self, dry_run: bool) -> Self {
+        Self { dry_run, ..self }
+    }
+
+    /// Set the refresh input flag
+    pub fn with_refresh_input(self, refresh_input: bool) -> Self {
+        Self {
+            refresh_input,
+            ..self
+        }
+    }
+
+    /// Set the flag to replace an existing `README.md` without part 2 in `ensure` mode
+    pub fn with_refresh_prompt(self, refresh_prompt: bool) -> Self {
+        Self {
+            refresh_prompt,
+            ..self
+        }
+    }
+
+    /// Set the flag to create a criterion benchmark
+    pub fn with_benches(self, with_benches: bool) -> Self {
+        Self {
+            with_benches,
+            ..self
+        }
+    }
+
+    /// Set the result types of star 1 and star 2, e.g., `"u64"` or `"String"`
+    ///
+    /// The generated placeholder solutions parse the result from a string, so the types must
+    /// implement [`FromStr`](std::str::FromStr). References such as `&str` are rejected by
+    /// [`write_files_with`].
+    pub fn with_result_types<S1: Into<String>, S2: Into<String>>(
+        self,
+        star1: S1,
+        star2: S2,
+    ) -> Self {
+        Self {
+            result_types: Some((star1.into(), star2.into())),
+            ..self
+        }
+    }
+
+    /// Set the Rust edition of the generated crate, e.g., `"2024"`
+    pub fn with_edition<S: Into<String>>(self, edition: S) -> Self {
+        Self {
+            edition: Some(edition.into()),
+            ..self
+        }
+    }
+
+    /// Set the minimum supported Rust version of the generated crate, e.g., `"1.70"`
+    pub fn with_rust_version<S: Into<String>>(self, rust_version: S) -> Self {
+        Self {
+            rust_version: Some(rust_version.into()),
+            ..self
+        }
+    }
+
+    /// Set the expected results of star 1 and star 2 for `example.txt` to create an integration
+    /// test `tests/example.rs`
+    pub fn with_example_test<S1: Into<String>, S2: Into<String>>(self, exp1: S1, exp2: S2) -> Self {
+        Self {
+            example_test: Some((exp1.into(), exp2.into())),
+            ..self
+        }
+    }
+
+    /// Set the expected result of star 1
+    pub fn with_exp1<S: Into<String>>(self, exp1: S) -> Self {
+        Self {
+            exp1: Some(exp1.into()),
+            ..self
+        }
+    }
+
+    /// Set the expected result of star 2
+    pub fn with_exp2<S: Into<String>>(self, exp2: S) -> Self {
+        Self {
+            exp2: Some(exp2.into()),
+            ..self
+        }
+    }
+
+    /// Set the line endings of files created from templates
+    pub fn with_line_ending(self, line_ending: LineEnding) -> Self {
+        Self {
+            line_ending,
+            ..self
+        }
+    }
+
+    /// Set the path to this crate used as `{LIB_DIR}` for the dependency in `Cargo.toml`
+    ///
+    /// The path is converted lossily to a string with `/` as separator, so that the generated
+    /// `Cargo.toml` is valid on all platforms.
+    pub fn with_lib_path<P: AsRef<Path>>(self, lib_path: P) -> Self {
+        Self {
+            lib_path: Some(lib_path.as_ref().to_path_buf()),
+            ..self
+        }
+    }
+
+    /// Set the source of the dependency on this crate in the generated `Cargo.toml`
+    pub fn with_dependency(self, dependency: DependencySpec) -> Self {
+        Self {
+            dependency: Some(dependency),
+            ..self
+        }
+    }
+
+    /// Set the path to a shared utility crate relative to the target directory to add it to
+    /// the dependencies of the generated `Cargo.toml`
+    pub fn with_util_crate<P: AsRef<Path>>(self, util_crate: P) -> Self {
+        Self {
+            util_crate: Some(util_crate.as_ref().to_path_buf()),
+            ..self
+        }
+    }
+
+    /// Set the command to run in the target directory after the files were written
+    pub fn with_post_hook(self, post_hook: PostHook) -> Self {
+        Self {
+            post_hook: Some(post_hook),
+            ..self
+        }
+    }
+
+    /// Set the flag to derive `Serialize` for the parsed input
+    ///
+    /// The generated `Cargo.toml` gets optional dependencies on `serde` and `serde_json` enabled
+    /// by a feature `serde`, so that builds without the feature stay free of them.
+    pub fn with_serde(self, serde: bool) -> Self {
+        Self { serde, ..self }
+    }
+
+    /// Set the flag to only write files which do not exist
+    pub fn with_ensure(self, ensure: bool) -> Self {
+        Self { ensure, ..self }
+    }
+
+    /// Set the flag to keep `input.txt` and `example.txt` under version control
+    ///
+    /// By default, inputs are added to the generated `.gitignore`, since puzzle inputs must not
+    /// be redistributed. Set this flag if your repository is not public.
+    pub fn with_commit_inputs(self, commit_inputs: bool) -> Self {
+        Self {
+            commit_inputs,
+            ..self
+        }
+    }
+
+    /// Set the flag to fail if the input is suspiciously short or looks like an error page
+    pub fn with_strict_input(self, strict_input: bool) -> Self {
+        Self {
+            strict_input,
+            ..self
+        }
+    }
+
+    /// Set the flag to keep backups of replaced files
+    pub fn with_backup(self, backup: bool) -> Self {
+        Self { backup, ..self }
+    }
+
+    /// Set the flag to format the generated Rust files with `rustfmt`
+    pub fn with_format(self, format: bool) -> Self {
+        Self { format, ..self }
+    }
+
+    /// Set the flag to check that the generated crate compiles
+    pub fn with_verify(self, verify: bool) -> Self {
+        Self { verify, ..self }
+    }
+
+    /// Get the paths relative to the target directory of all files [`write_files_with`] may
+    /// write with these options, in the order they are written
+    ///
+    /// `README.md` is only written if the input provider supplies a puzzle description.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_aoc::template::WriteOptions;
+    /// let manifest = WriteOptions::new("day01").manifest();
+    /// assert_eq!(Some(&"input.txt"), manifest.first());
+    /// assert!(manifest.contains(&"src/lib.rs"));
+    /// assert!(!manifest.contains(&"benches/bench.rs"));
+    /// assert!(WriteOptions::new("day01").with_benches(true).manifest().contains(&"benches/bench.rs"));
+    /// ```
+    pub fn manifest(&self) -> Vec<&'static str> {
+        let mut manifest = vec!["input.txt", "example.txt", "README.md"];
+        manifest.extend(TEMPLATE_FILES.iter().map(|(_, file)| *file));
+        if self.with_benches {
+            manifest.push(BENCH_FILE);
+        }
+        if self.example_test.is_some() {
+            manifest.push(EXAMPLE_TEST_FILE);
+        }
+        manifest
+    }
+
+    /// Get the options for a single `day` of a `year` with the target directory rendered as a
+    /// template with the variables `{YEAR}`, `{YEAR2}`, `{YEAR4}`, `{DAY}`, and `{DAY2}`
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_aoc::template::WriteOptions;
+    /// # use std::path::Path;
+    /// let opts = WriteOptions::new("day{DAY2}/rust").for_day(2022, 5);
+    /// assert_eq!(Path::new("day05/rust"), opts.path);
+    /// ```
+    pub fn for_day(&self, year: u16, day: u16) -> Self {
+        let path = render_template(&self.path.to_string_lossy(), &date_vars(year, day));
+        Self {
+            path: PathBuf::from(path),
+            ..self.clone()
+        }
+    }
+
+    /// Set the workspace root to scan for package name collisions
+    pub fn with_workspace_root<P: AsRef<Path>>(self, workspace_root: P) -> Self {
+        Self {
+            workspace_root: Some(workspace_root.as_ref().to_path_buf()),
+            ..self
+        }
+    }
+}
+
+/// Write files for a new puzzle to the directory at `path` and return the paths of all files
+/// written in the order of creation
+///
+/// This is a wrapper for [`write_files_with`] with the built-in templates, see there for
+/// details. All other options are set with [`WriteOptions`].
 pub fn write_files<F, S>(
     path: &Path,
     input_provider: &dyn InputProvider,
@@ -9,174 +571,3164 @@ pub fn write_files<F, S>(
     year: u16,
     day: u16,
     force: bool,
-) -> Result<(), PuzzleError>
+) -> Result<Vec<PathBuf>, PuzzleError>
 where
     S: AsRef<str>,
     F: FnOnce() -> S,
 {
-    let vars = build_var_map(config, year, day);
+    let opts = WriteOptions::new(path)
+        .with_config(config().as_ref())
+        .with_force(force);
+    write_files_with(year, day, input_provider, &opts)
+}
+
+/// Write files for a new puzzle as specified by `opts` and return the paths of all files
+/// written in the order of creation
+///
+/// The files are created from the given templates, see [`TemplateSet`] for the variables
+/// available.
+///
+/// An existing `input.txt` is kept, even if `force` is set, and the input provider is not
+/// called, unless `refresh_input` is set.
+///
+/// An empty `example.txt` for example inputs used in tests is created unless it exists.
+///
+/// If the input provider supports it, the puzzle description is written to `README.md`. Failing
+/// to load the description is not considered an error. A description without part 2, see
+/// [`has_part_two`], gets a note that part 2 is still locked.
+///
+/// If `dry_run` is set, the files that would be written are logged but nothing is written
+/// to the file system and the input provider is not called.
+///
+/// If `with_benches` is set, a criterion benchmark `benches/bench.rs` is created and the
+/// corresponding entries are added to `Cargo.toml`.
+///
+/// If a `workspace_root` is set, it is scanned for a package with the same name as the new
+/// crate, other than the crate in the target directory itself. If one is found, an error of
+/// kind [`std::io::ErrorKind::AlreadyExists`] naming the conflicting manifest is returned
+/// before any file is written.
+///
+/// If `backup` is set, files in an existing target directory are overwritten as if `force` was
+/// set, but every file replaced with different content is kept as `{name}.bak`. If that exists
+/// already, `{name}.bak.1`, `{name}.bak.2`, ... is used so that previous backups are not lost.
+///
+/// If `ensure` is set, only files which do not exist are written, existing files are left
+/// untouched. This allows to repair a directory, e.g., after deleting a file by accident. If
+/// `refresh_prompt` is set as well, a `README.md` without part 2 is replaced, so that the
+/// description is completed once part 1 is solved.
+///
+/// An input shorter than [`MIN_INPUT_LEN`] after trimming whitespace or containing one of the
+/// [`INPUT_ERROR_MARKERS`] almost always indicates a problem, e.g., an expired session. A
+/// warning is logged, or, if `strict_input` is set, an error is returned before any file is
+/// written.
+///
+/// The generated `.gitignore` excludes `input.txt` and `example.txt`, since puzzle inputs must
+/// not be redistributed, unless `commit_inputs` is set.
+///
+/// If `format` is set, the Rust files written are formatted with `rustfmt` unless `dry_run` is
+/// set. If `rustfmt` is not available or fails, a warning is logged and the files are kept as
+/// they are.
+///
+/// If `verify` is set, the generated crate is checked with [`verify_generated`] unless
+/// `dry_run` is set.
+///
+/// The rendered `Cargo.toml` is parsed before any file is written, so that a broken template
+/// or variable, e.g., an unsubstituted `{AUTHORS}`, is reported right away instead of at the
+/// first `cargo build`.
+///
+/// If writing fails, e.g., because the input cannot be loaded, the files and directories
+/// created so far are removed again, so that the call can simply be repeated. Pre-existing
+/// files and directories are never removed, files overwritten are restored and backups created
+/// are removed. A failed verification keeps the files for
+/// inspection.
+///
+/// If a `post_hook` is set, it is run in the target directory once all files are written and
+/// verified unless `dry_run` is set. A failing hook keeps the files as well.
+pub fn write_files_with(
+    year: u16,
+    day: u16,
+    input_provider: &dyn InputProvider,
+    opts: &WriteOptions,
+) -> Result<Vec<PathBuf>, PuzzleError> {
+    let WriteOptions {
+        path,
+        force,
+        dry_run,
+        workspace_root,
+        verify,
+        backup,
+        ensure,
+        format,
+        ..
+    } = opts;
+    let (force, dry_run, verify, backup, ensure) = (*force, *dry_run, *verify, *backup, *ensure);
+
+    validate(year, day)?;
+
+    if path.exists() && !path.is_dir() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "The target path '{}' is a file, expected a directory",
+                path.to_string_lossy()
+            ),
+        )
+        .into());
+    }
 
-    if path.exists() && !force {
+    if path.exists() && !force && !backup && !ensure {
         return Err(PuzzleError::from(format!(
-            "The target directory '{}' exists. Use the --force option to overwrite.",
+            "The target directory '{}' exists. Use the --force, --backup, or --ensure option.",
             path.to_string_lossy()
         )));
     }
 
-    let src_path = path.join("src");
-    println!("Creating directories for {}", src_path.to_string_lossy());
-    fs::create_dir_all(src_path.as_path())?;
+    if let Some((result1_type, result2_type)) = &opts.result_types {
+        for result_type in [result1_type, result2_type] {
+            if result_type.trim_start().starts_with('&') {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!(
+                        "Unsupported result type '{result_type}', expected an owned type \
+                        implementing FromStr, e.g., String"
+                    ),
+                )
+                .into());
+            }
+        }
+    }
 
-    // input file from provided input
-    let input = input_provider.load_input(year, day)?;
-    write_file(&input, &HashMap::new(), path.join("input.txt").as_path())?;
+    let files = render_files(year, day, opts);
+    let mut edition = DEFAULT_EDITION.to_string();
+    if let Some((_, cargo_toml)) = files.iter().find(|(file, _)| *file == "Cargo.toml") {
+        validate_cargo_toml(cargo_toml)?;
+        edition = package_edition(cargo_toml).unwrap_or(edition);
+    }
 
-    // other files from templates
-    write_file(GITIGNORE, &vars, path.join(".gitignore").as_path())?;
-    write_file(CARGO_TOML, &vars, path.join("Cargo.toml").as_path())?;
-    write_file(README_ADOC, &vars, path.join("README.adoc").as_path())?;
-    write_file(MAIN_RS, &vars, src_path.join("main.rs").as_path())?;
-    write_file(LIB_RS, &vars, src_path.join("lib.rs").as_path())?;
+    if let Some(workspace_root) = workspace_root {
+        let cargo_toml = files.iter().find(|(file, _)| *file == "Cargo.toml");
+        if let Some(name) = cargo_toml.and_then(|(_, content)| package_name(content)) {
+            let own_manifest = fs::canonicalize(path.join("Cargo.toml")).ok();
+            if let Some(conflict) = find_package(workspace_root, &name)?
+                .into_iter()
+                .find(|manifest| fs::canonicalize(manifest).ok() != own_manifest)
+            {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::AlreadyExists,
+                    format!(
+                        "A package named '{name}' already exists at '{}'",
+                        conflict.to_string_lossy()
+                    ),
+                )
+                .into());
+            }
+        }
+    }
 
-    Ok(())
-}
+    let mut created = Created::default();
+    let paths = match write_all(year, day, input_provider, opts, files, &mut created) {
+        Ok(paths) => paths,
+        Err(err) => {
+            created.roll_back();
+            return Err(err);
+        }
+    };
 
-fn write_file(
-    template: &str,
-    vars: &HashMap<String, String>,
-    path: &Path,
-) -> Result<(), PuzzleError> {
-    let content = replace_vars(template, vars);
+    if *format && !dry_run {
+        format_generated(&paths, &edition);
+    }
 
-    println!("Writing file {} ...", path.to_string_lossy());
-    fs::write(path, content)?;
+    if verify && !dry_run {
+        verify_generated(path)?;
+    }
 
-    Ok(())
-}
+    if let Some(post_hook) = opts.post_hook.as_ref().filter(|_| !dry_run) {
+        post_hook.run(path, year, day)?;
+    }
 
-pub trait InputProvider {
-    fn load_input(&self, year: u16, day: u16) -> Result<String, PuzzleError>;
+    Ok(paths)
 }
 
-impl InputProvider for PuzzleIO {
-    fn load_input(&self, year: u16, day: u16) -> Result<String, PuzzleError> {
-        PuzzleIO::load_input(self, year, day)
-    }
+/// Write files for a new puzzle like [`write_files_with`], but start from `src/lib.rs` and
+/// `src/main.rs` of the existing day crate at `source_path` instead of the templates
+///
+/// References to the source crate, e.g., `mr_kaffee_2022_5`, the `year` and `day` fields of the
+/// puzzle, and the puzzle URLs are rewritten for the new day. Expected results are reset to
+/// `None`, since they refer to the input of the source day. The input, `Cargo.toml`, and all
+/// other files are created as usual. If the source has no `src/main.rs`, the template is used.
+///
+/// The source has to be a day crate, i.e., have a `Cargo.toml` with a package named
+/// `mr-kaffee-{year}-{day}` and a `src/lib.rs`. Otherwise, an error of kind
+/// [`std::io::ErrorKind::InvalidInput`] is returned and nothing is written.
+pub fn write_files_from_existing(
+    source_path: &Path,
+    year: u16,
+    day: u16,
+    input_provider: &dyn InputProvider,
+    opts: &WriteOptions,
+) -> Result<Vec<PathBuf>, PuzzleError> {
+    let (source_year, source_day, source_name) = day_crate(source_path)?;
+    let vars = build_var_map(|| &opts.config, year, day);
+    let target_name = render_template("mr_kaffee_{YEAR}_{CRATE_DAY}", &vars);
+
+    let crate_re = Regex::new(&format!(r"\b{}\b", source_name.replace('-', "_"))).unwrap();
+    let year_re = Regex::new(r"(?m)^(\s*year:\s*)\d+,").unwrap();
+    let day_re = Regex::new(r"(?m)^(\s*day:\s*)\d+,").unwrap();
+    let url_re = Regex::new(&format!(r"\b{source_year}/day/{source_day}\b")).unwrap();
+    let rewrite = |content: &str| {
+        let content = crate_re.replace_all(content, target_name.as_str());
+        let content = year_re.replace_all(&content, format!("${{1}}{year},"));
+        let content = day_re.replace_all(&content, format!("${{1}}{day},"));
+        let content = url_re.replace_all(&content, format!("{year}/day/{day}"));
+        // the copied files are used as templates, so braces have to be escaped
+        reset_expected(&content)
+            .replace('{', "{{")
+            .replace('}', "}}")
+    };
+
+    let lib_path = source_path.join("src").join("lib.rs");
+    let lib_rs = rewrite(&fs::read_to_string(&lib_path).map_err(io_context("read", &lib_path))?);
+    let main_path = source_path.join("src").join("main.rs");
+    let main_rs = match main_path.is_file() {
+        true => rewrite(&fs::read_to_string(&main_path).map_err(io_context("read", &main_path))?),
+        false => opts.templates.main_rs.clone(),
+    };
+
+    let templates = TemplateSet {
+        lib_rs,
+        main_rs,
+        ..opts.templates.clone()
+    };
+    write_files_with(
+        year,
+        day,
+        input_provider,
+        &opts.clone().with_template_set(templates),
+    )
 }
 
-pub fn upd_files<F, S>(path: &Path, config: F, year: u16, day: u16) -> Result<(), PuzzleError>
-where
-    S: AsRef<str>,
-    F: FnOnce() -> S,
-{
-    let vars = build_var_map(config, year, day);
-    let separator = "INCLUDE_PUZZLES";
+/// get the year, the day, and the package name of the day crate at `path`, see
+/// [`write_files_from_existing`]
+fn day_crate(path: &Path) -> Result<(u16, u16, String), PuzzleError> {
+    let invalid = |reason: String| -> PuzzleError {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("'{}' is not a day crate: {reason}", path.to_string_lossy()),
+        )
+        .into()
+    };
 
-    let line = replace_vars(PUZZLE_FACTORY_SNIPPET, &vars);
-    upd_file(separator, &line, path.join("src/main.rs").as_path())?;
+    let cargo_toml = fs::read_to_string(path.join("Cargo.toml"))
+        .map_err(|err| invalid(format!("Cannot read Cargo.toml: {err}")))?;
+    let name = package_name(&cargo_toml)
+        .ok_or_else(|| invalid("Cargo.toml has no package name".to_string()))?;
+    let captures = Regex::new(r"^mr-kaffee-(\d{4})-(\d{1,2})$")
+        .unwrap()
+        .captures(&name)
+        .ok_or_else(|| {
+            invalid(format!(
+                "package '{name}' is not named mr-kaffee-{{year}}-{{day}}"
+            ))
+        })?;
+    if !path.join("src").join("lib.rs").is_file() {
+        return Err(invalid("src/lib.rs not found".to_string()));
+    }
 
-    let line = replace_vars(PUZZLE_INCLUDE_SNIPPET, &vars);
-    upd_file(separator, &line, path.join("Cargo.toml").as_path())?;
+    // both captures are numbers of at most four digits
+    let year = captures[1].parse().unwrap();
+    let day = captures[2].parse().unwrap();
+    Ok((year, day, name))
+}
 
-    Ok(())
+/// replace the expected results `exp: Some(...)` in `source` by `exp: None`
+fn reset_expected(source: &str) -> String {
+    const EXP: &str = "exp: Some(";
+
+    let mut result = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(start) = rest.find(EXP) {
+        let args = &rest[start + EXP.len()..];
+        let mut depth = 1;
+        let Some(len) = args.find(|c| {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => (),
+            }
+            depth == 0
+        }) else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        result.push_str("exp: None");
+        rest = &args[len + 1..];
+    }
+    result.push_str(rest);
+    result
 }
 
-fn upd_file(separator: &str, line: &str, path: &Path) -> Result<bool, PuzzleError> {
-    println!("Updating file {} ...", path.to_string_lossy());
+/// Create the directories and write the files for [`write_files_with`], record everything that
+/// did not exist before in `created`
+fn write_all(
+    year: u16,
+    day: u16,
+    input_provider: &dyn InputProvider,
+    opts: &WriteOptions,
+    files: Vec<(&'static str, String)>,
+    created: &mut Created,
+) -> Result<Vec<PathBuf>, PuzzleError> {
+    let WriteOptions {
+        path,
+        dry_run,
+        refresh_input,
+        refresh_prompt,
+        backup,
+        ensure,
+        strict_input,
+        ..
+    } = opts;
+    let (dry_run, refresh_input, backup, ensure) = (*dry_run, *refresh_input, *backup, *ensure);
 
-    let re = Regex::new(
-        format!(r"(?ms:(?P<prefix>^.*{separator}:START.*?[\r\n]+)(?P<indent>\s*)(?P<data>.*?{separator}:END)(?P<suffix>.*$))")
-            .as_str(),
-    )
-    .unwrap();
+    let src_path = path.join("src");
+    if dry_run {
+        info!(
+            "Would create directories for {}",
+            src_path.to_string_lossy()
+        );
+    } else {
+        info!("Creating directories for {}", src_path.to_string_lossy());
+        created.create_dir_all(&src_path)?;
+    }
 
-    let s = fs::read_to_string(path)?;
-    if let Some(captures) = re.captures(s.as_str()) {
-        // if regex matches, those groups exist
-        let prefix = captures.name("prefix").unwrap().as_str();
-        let indent = captures.name("indent").unwrap().as_str();
-        let data = captures.name("data").unwrap().as_str();
-        let suffix = captures.name("suffix").unwrap().as_str();
+    let mut paths = Vec::new();
 
-        if !data.contains(line) {
-            let contents = format!("{prefix}{indent}{line}\n{indent}{data}{suffix}");
-            fs::write(path, contents)?;
-            println!("-> Updated");
-            Ok(true)
+    // input file from provided input
+    let input_path = path.join("input.txt");
+    if input_path.exists() && !refresh_input {
+        info!(
+            "Keeping existing input {}, use refresh input to replace it",
+            input_path.to_string_lossy()
+        );
+    } else if dry_run {
+        info!("Would write input to {}", input_path.to_string_lossy());
+        paths.push(input_path);
+    } else {
+        let input = input_provider.load_input(year, day)?;
+        let trimmed = input.trim();
+        let problem = if trimmed.len() < MIN_INPUT_LEN {
+            Some(format!("has only {} bytes", trimmed.len()))
         } else {
-            println!("-> Nothing to update");
-            Ok(false)
+            INPUT_ERROR_MARKERS
+                .iter()
+                .find(|marker| trimmed.contains(*marker))
+                .map(|marker| format!("looks like an error page containing '{marker}'"))
+        };
+        if let Some(problem) = problem {
+            let message = format!("The input for {year}/{day} {problem}, check your session");
+            if *strict_input {
+                return Err(message.into());
+            }
+            warn!("{message}");
         }
-    } else {
-        println!("-> No section to update ({separator}:START ... {separator}:END) found");
-        Ok(false)
+        paths.push(created.write_file(&input, input_path, dry_run, backup)?);
     }
-}
 
-const MAIN_RS: &str = include_str!("../templates/_main.rs_");
-const LIB_RS: &str = include_str!("../templates/_lib.rs_");
-const README_ADOC: &str = include_str!("../templates/_README.adoc_");
-const CARGO_TOML: &str = include_str!("../templates/_Cargo.toml_");
-const GITIGNORE: &str = include_str!("../templates/_.gitignore_");
-const PUZZLE_FACTORY_SNIPPET: &str = include_str!("../templates/_puzzle_factory_snippet_");
-const PUZZLE_INCLUDE_SNIPPET: &str = include_str!("../templates/_puzzle_include_snippet_");
+    // empty example input file, never overwritten
+    let example_path = path.join("example.txt");
+    if !example_path.exists() {
+        paths.push(created.write_file("", example_path, dry_run, false)?);
+    }
 
-/// build variables map, the function `config` is used to load custom configuration,
-/// the standard use case is `config = read_config`
-fn build_var_map<F, S>(config: F, year: u16, day: u16) -> HashMap<String, String>
-where
-    S: AsRef<str>,
-    F: FnOnce() -> S,
-{
-    let mut vars = HashMap::from([
-        ("YEAR".to_string(), year.to_string()),
-        ("YEAR2".to_string(), format!("{:02}", year % 100)),
-        (
-            "YEAR4".to_string(),
-            format!("{:04}", if year < 100 { year + 2000 } else { year }),
-        ),
-        ("DAY".to_string(), day.to_string()),
-        ("DAY2".to_string(), format!("{day:02}")),
-    ]);
+    // puzzle description from provider
+    // a description still missing part 2 is not final and loaded again on request
+    let readme_path = path.join("README.md");
+    let skip_prompt = dry_run
+        || (ensure
+            && readme_path.exists()
+            && (!*refresh_prompt
+                || fs::read_to_string(&readme_path).map_or(true, |readme| has_part_two(&readme))));
+    if !skip_prompt {
+        match input_provider.load_prompt(year, day) {
+            Ok(Some(mut prompt)) => {
+                if !has_part_two(&prompt) {
+                    info!("Part 2 of {year}/{day} is still locked");
+                    prompt.push_str(PART_TWO_LOCKED_NOTE);
+                }
+                paths.push(created.write_file(&prompt, readme_path, dry_run, backup)?)
+            }
+            Ok(None) => (),
+            Err(err) => warn!("Could not load puzzle description: {err}"),
+        }
+    }
 
-    let mut configs = match serde_json::from_str::<HashMap<String, String>>(config().as_ref()) {
-        Ok(configs) => configs,
-        Err(err) => {
-            println!("Could not parse config JSON: {err}");
-            HashMap::new()
+    // other files from templates
+    for (file, content) in files {
+        let file_path = file
+            .split('/')
+            .fold(path.to_path_buf(), |p, part| p.join(part));
+        if ensure && file_path.exists() {
+            continue;
         }
-    };
-    for value in configs.values_mut() {
-        *value = replace_vars(value, &vars);
+        if let Some(parent) = file_path.parent().filter(|_| !dry_run) {
+            created.create_dir_all(parent)?;
+        }
+        paths.push(created.write_file(&content, file_path, dry_run, backup)?);
     }
 
-    vars.extend(configs);
+    Ok(paths)
+}
 
-    vars
+/// note appended to a puzzle description without part 2, see [`has_part_two`]
+const PART_TWO_LOCKED_NOTE: &str =
+    "\n\n_Part 2 is still locked, solve part 1 and create the files again with `--ensure --refresh-prompt` to update this description._\n";
+
+/// Files and directories created by [`write_files_with`], removed again if it fails
+///
+/// The original content of files overwritten is recorded as well, so that it is restored if
+/// writing fails. Backups created in the meantime are removed.
+#[derive(Debug, Default)]
+struct Created {
+    paths: Vec<PathBuf>,
+    overwritten: Vec<(PathBuf, Vec<u8>)>,
 }
 
-/// replace variables in given template
-fn replace_vars(template: &str, vars: &HashMap<String, String>) -> String {
-    let mut content = template.to_string();
-    for (name, value) in vars {
-        content = content.replace(format!("{{{name}}}").as_str(), value);
+impl Created {
+    /// create `path` with its parents and record the directories which did not exist
+    fn create_dir_all(&mut self, path: &Path) -> Result<(), PuzzleError> {
+        let missing = path
+            .ancestors()
+            .take_while(|dir| !dir.as_os_str().is_empty() && !dir.exists())
+            .map(Path::to_path_buf)
+            .collect::<Vec<_>>();
+        // record before creating, directories created before a failure are removed as well
+        self.paths.extend(missing.into_iter().rev());
+        fs::create_dir_all(path).map_err(io_context("create", path))
+    }
+
+    /// write a file with [`write_file`] and record it if it did not exist, or its original
+    /// content and the backup created, if any, if it did
+    fn write_file(
+        &mut self,
+        content: &str,
+        path: PathBuf,
+        dry_run: bool,
+        backup: bool,
+    ) -> Result<PathBuf, PuzzleError> {
+        if !dry_run && path.is_file() {
+            let original = fs::read(&path).map_err(io_context("read", &path))?;
+            if backup && original != content.as_bytes() {
+                self.paths.push(backup_path(&path));
+            }
+            self.overwritten.push((path.clone(), original));
+        } else if !dry_run && !path.exists() {
+            self.paths.push(path.clone());
+        }
+        write_file(content, path, dry_run, backup)
+    }
+
+    /// restore the files overwritten and remove everything recorded in reverse order, other
+    /// pre-existing files and directories are never touched
+    fn roll_back(self) {
+        for (path, original) in self.overwritten.into_iter().rev() {
+            match fs::write(&path, original) {
+                Ok(()) => info!("Restored {}", path.to_string_lossy()),
+                Err(err) => warn!("Could not restore {}: {err}", path.to_string_lossy()),
+            }
+        }
+        for path in self.paths.into_iter().rev() {
+            let result = if path.is_dir() {
+                fs::remove_dir(&path)
+            } else {
+                fs::remove_file(&path)
+            };
+            match result {
+                Ok(()) => info!("Removed {}", path.to_string_lossy()),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => (),
+                Err(err) => warn!("Could not remove {}: {err}", path.to_string_lossy()),
+            }
+        }
+    }
+}
+
+/// Render the files from templates, see [`TEMPLATE_FILES`], [`BENCH_FILE`], and
+/// [`EXAMPLE_TEST_FILE`], with paths
+/// relative to the target directory
+fn render_files(year: u16, day: u16, opts: &WriteOptions) -> Vec<(&'static str, String)> {
+    let WriteOptions {
+        templates,
+        config,
+        with_benches,
+        result_types,
+        edition,
+        rust_version,
+        example_test,
+        exp1,
+        exp2,
+        line_ending,
+        lib_path,
+        commit_inputs,
+        serde,
+        dependency,
+        util_crate,
+        ..
+    } = opts;
+
+    let mut vars = build_var_map(|| config, year, day);
+    if let Some((result1_type, result2_type)) = result_types {
+        vars.insert("RESULT1_TYPE".to_string(), result1_type.clone());
+        vars.insert("RESULT2_TYPE".to_string(), result2_type.clone());
+    }
+    if let Some(lib_path) = lib_path {
+        vars.insert("LIB_DIR".to_string(), slash_path(lib_path));
+    }
+    if let Some(edition) = edition {
+        vars.insert("EDITION".to_string(), edition.clone());
+    }
+    // the dependency defaults to the path {LIB_DIR}, which is left as placeholder if unknown
+    let default_dependency = match vars.get("LIB_DIR") {
+        Some(lib_dir) => DependencySpec::Path(PathBuf::from(lib_dir)).to_toml(),
+        None => r#"{ path = "{LIB_DIR}" }"#.to_string(),
+    };
+    match dependency {
+        Some(dependency) => {
+            vars.insert("AOC_DEPENDENCY".to_string(), dependency.to_toml());
+        }
+        None => {
+            vars.entry("AOC_DEPENDENCY".to_string())
+                .or_insert(default_dependency);
+        }
+    }
+    if *commit_inputs {
+        vars.insert("IGNORED_INPUTS".to_string(), String::new());
+    }
+    if *serde {
+        vars.insert("SERDE_DERIVE".to_string(), SERDE_DERIVE.to_string());
+    }
+    for (name, exp, result_type) in [
+        ("EXP1", exp1, "RESULT1_TYPE"),
+        ("EXP2", exp2, "RESULT2_TYPE"),
+    ] {
+        if let Some(exp) = exp {
+            let exp = exp_expression(exp, &vars[result_type]);
+            vars.insert(name.to_string(), exp);
+        }
+    }
+    if let Some((exp1, exp2)) = example_test {
+        for (name, exp, result_type) in [
+            ("EXAMPLE_EXP1", exp1, "RESULT1_TYPE"),
+            ("EXAMPLE_EXP2", exp2, "RESULT2_TYPE"),
+        ] {
+            let exp = exp_expression(exp, &vars[result_type]);
+            vars.insert(name.to_string(), exp);
+        }
+    }
+
+    let cargo_toml = if *with_benches {
+        format!("{}{}", templates.cargo_toml, templates.bench_cargo_toml)
+    } else {
+        templates.cargo_toml.clone()
+    };
+
+    let mut files = TEMPLATE_FILES
+        .iter()
+        .map(|(template_file, file)| {
+            let template = match template_file {
+                TemplateFile::Gitignore => &templates.gitignore,
+                TemplateFile::CargoToml => &cargo_toml,
+                TemplateFile::ReadmeAdoc => &templates.readme_adoc,
+                TemplateFile::MainRs => &templates.main_rs,
+                TemplateFile::LibRs => &templates.lib_rs,
+            };
+            let mut content = render_template(template, &vars);
+            if let (TemplateFile::CargoToml, Some(util_crate)) = (template_file, util_crate) {
+                let util = DependencySpec::Path(util_crate.clone());
+                match add_dependency(&content, UTIL_CRATE_NAME, &util) {
+                    Ok(updated) => content = updated,
+                    Err(err) => warn!("Could not add {UTIL_CRATE_NAME} to {file}: {err}"),
+                }
+            }
+            if let (TemplateFile::CargoToml, Some(rust_version)) = (template_file, rust_version) {
+                match set_rust_version(&content, rust_version) {
+                    Ok(updated) => content = updated,
+                    Err(err) => warn!("Could not set the rust-version in {file}: {err}"),
+                }
+            }
+            if let (TemplateFile::CargoToml, true) = (template_file, *serde) {
+                match add_serde_feature(&content) {
+                    Ok(updated) => content = updated,
+                    Err(err) => warn!("Could not add the serde feature to {file}: {err}"),
+                }
+            }
+            (*file, line_ending.apply(&content))
+        })
+        .collect::<Vec<_>>();
+    if *with_benches {
+        let content = line_ending.apply(&render_template(&templates.bench_rs, &vars));
+        files.push((BENCH_FILE, content));
+    }
+    if example_test.is_some() {
+        let content = line_ending.apply(&render_template(&templates.example_rs, &vars));
+        files.push((EXAMPLE_TEST_FILE, content));
+    }
+
+    files
+}
+
+/// Write files for several `days` of a `year` with [`write_files_with`] and return the result
+/// for every day
+///
+/// The target directory for every day is obtained with [`WriteOptions::for_day`], e.g.,
+/// `day{DAY2}/rust/mr-kaffee`. A failure for one day, e.g., because the puzzle is not yet
+/// unlocked, does not abort the other days.
+pub fn write_files_range(
+    input_provider: &dyn InputProvider,
+    year: u16,
+    days: RangeInclusive<u16>,
+    opts: &WriteOptions,
+) -> Vec<(u16, Result<Vec<PathBuf>, PuzzleError>)> {
+    days.map(|day| {
+        let opts = opts.for_day(year, day);
+        (day, write_files_with(year, day, input_provider, &opts))
+    })
+    .collect()
+}
+
+/// Difference of a file rendered from a template to the file on disk, see [`diff_files`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileDiff {
+    /// the file exists with the rendered content
+    Unchanged,
+    /// the file exists with different content, the unified diff from the existing file to the
+    /// rendered file, or a note if only the line endings differ
+    Modified(String),
+    /// the file does not exist
+    New,
+}
+
+/// Compare the files [`write_files_with`] would render from templates to the files in
+/// `target_path`
+///
+/// Nothing is written. The input and the puzzle description are not compared. The target
+/// directory of `opts` is ignored in favor of `target_path`, so that the same options can be
+/// used for several days, see [`WriteOptions::for_day`].
+///
+/// # Examples
+/// ```
+/// # use mr_kaffee_aoc::template::{diff_files, FileDiff, WriteOptions};
+/// let diffs = diff_files("target/does_not_exist", 2022, 1, &WriteOptions::new("."));
+/// assert!(diffs.iter().all(|(_, diff)| *diff == FileDiff::New));
+/// ```
+pub fn diff_files<P: AsRef<Path>>(
+    target_path: P,
+    year: u16,
+    day: u16,
+    opts: &WriteOptions,
+) -> Vec<(PathBuf, FileDiff)> {
+    let target_path = target_path.as_ref();
+    render_files(year, day, opts)
+        .into_iter()
+        .map(|(file, content)| {
+            let path = file
+                .split('/')
+                .fold(target_path.to_path_buf(), |p, part| p.join(part));
+            let diff = match fs::read_to_string(&path) {
+                Ok(existing) if existing == content => FileDiff::Unchanged,
+                // a line based diff would be empty
+                Ok(existing) if existing.replace("\r\n", "\n") == content.replace("\r\n", "\n") => {
+                    FileDiff::Modified(format!("--- a/{file}\n+++ b/{file}\nline endings differ\n"))
+                }
+                Ok(existing) => FileDiff::Modified(unified_diff(&existing, &content, file)),
+                Err(_) => FileDiff::New,
+            };
+            (path, diff)
+        })
+        .collect()
+}
+
+/// number of unchanged lines shown around changes by [`unified_diff`]
+const DIFF_CONTEXT: usize = 3;
+
+/// line of an edit script, the indices refer to the old and the new lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffLine {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+/// Unified diff from `old` to `new` with headers `a/{name}` and `b/{name}`
+///
+/// The edit script is obtained from a longest common subsequence of lines, which is fine for
+/// the small files rendered from templates.
+fn unified_diff(old: &str, new: &str, name: &str) -> String {
+    let a = old.lines().collect::<Vec<_>>();
+    let b = new.lines().collect::<Vec<_>>();
+
+    // lcs[i][j] is the length of the longest common subsequence of a[i..] and b[j..]
+    let w = b.len() + 1;
+    let mut lcs = vec![0; (a.len() + 1) * w];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i * w + j] = if a[i] == b[j] {
+                lcs[(i + 1) * w + j + 1] + 1
+            } else {
+                lcs[(i + 1) * w + j].max(lcs[i * w + j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            lines.push(DiffLine::Equal(i, j));
+            (i, j) = (i + 1, j + 1);
+        } else if i < a.len() && (j == b.len() || lcs[(i + 1) * w + j] >= lcs[i * w + j + 1]) {
+            lines.push(DiffLine::Delete(i));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Insert(j));
+            j += 1;
+        }
+    }
+
+    let changes = (0..lines.len())
+        .filter(|&k| !matches!(lines[k], DiffLine::Equal(..)))
+        .collect::<Vec<_>>();
+    let mut diff = format!("--- a/{name}\n+++ b/{name}\n");
+    let mut k = 0;
+    while k < changes.len() {
+        // join changes separated by at most twice the context into one hunk
+        let start = changes[k].saturating_sub(DIFF_CONTEXT);
+        while k + 1 < changes.len() && changes[k + 1] <= changes[k] + 2 * DIFF_CONTEXT + 1 {
+            k += 1;
+        }
+        let end = (changes[k] + DIFF_CONTEXT + 1).min(lines.len());
+        k += 1;
+
+        let hunk = &lines[start..end];
+        let count = |skip: fn(&DiffLine) -> bool, lines: &[DiffLine]| {
+            lines.iter().filter(|line| !skip(line)).count()
+        };
+        let is_insert = |line: &DiffLine| matches!(line, DiffLine::Insert(_));
+        let is_delete = |line: &DiffLine| matches!(line, DiffLine::Delete(_));
+        let (old_len, new_len) = (count(is_insert, hunk), count(is_delete, hunk));
+        // ranges are 1-based, empty ranges refer to the line before
+        let old_start = count(is_insert, &lines[..start]) + usize::from(old_len > 0);
+        let new_start = count(is_delete, &lines[..start]) + usize::from(new_len > 0);
+        diff.push_str(&format!(
+            "@@ -{old_start},{old_len} +{new_start},{new_len} @@\n"
+        ));
+        for line in hunk {
+            let (prefix, text) = match *line {
+                DiffLine::Equal(i, _) => (' ', a[i]),
+                DiffLine::Delete(i) => ('-', a[i]),
+                DiffLine::Insert(j) => ('+', b[j]),
+            };
+            diff.push_str(&format!("{prefix}{text}\n"));
+        }
+    }
+
+    diff
+}
+
+/// templates rendered by [`write_files_with`]
+#[derive(Debug, Clone, Copy)]
+enum TemplateFile {
+    Gitignore,
+    CargoToml,
+    ReadmeAdoc,
+    MainRs,
+    LibRs,
+}
+
+/// files rendered from templates by [`write_files_with`] with paths relative to the target
+/// directory, in the order they are written
+const TEMPLATE_FILES: [(TemplateFile, &str); 5] = [
+    (TemplateFile::Gitignore, ".gitignore"),
+    (TemplateFile::CargoToml, "Cargo.toml"),
+    (TemplateFile::ReadmeAdoc, "README.adoc"),
+    (TemplateFile::MainRs, "src/main.rs"),
+    (TemplateFile::LibRs, "src/lib.rs"),
+];
+
+/// benchmark written by [`write_files_with`] if `with_benches` is set
+const BENCH_FILE: &str = "benches/bench.rs";
+
+/// integration test written by [`write_files_with`] if `example_test` is set
+const EXAMPLE_TEST_FILE: &str = "tests/example.rs";
+
+/// package name of the shared utility crate, see [`scaffold_util_crate`]
+pub const UTIL_CRATE_NAME: &str = "mr-kaffee-util";
+
+/// files of the shared utility crate created by [`scaffold_util_crate`]
+const UTIL_FILES: [(&str, &str); 2] = [
+    ("Cargo.toml", include_str!("../templates/_util_Cargo.toml_")),
+    ("src/lib.rs", include_str!("../templates/_util_lib.rs_")),
+];
+
+/// Create a skeleton of the shared utility crate [`UTIL_CRATE_NAME`] in the directory at
+/// `path` and return the paths of all files written
+///
+/// Files which exist are kept, so that calling this function again does not discard any
+/// helpers. Days depend on the crate if created with [`WriteOptions::with_util_crate`].
+pub fn scaffold_util_crate<P: AsRef<Path>>(path: P) -> Result<Vec<PathBuf>, PuzzleError> {
+    let path = path.as_ref();
+    let mut paths = Vec::new();
+    for (file, content) in UTIL_FILES {
+        let file_path = path.join(file);
+        if file_path.exists() {
+            info!("Keeping {}", file_path.to_string_lossy());
+            continue;
+        }
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent).map_err(io_context("create", parent))?;
+        }
+        fs::write(&file_path, content).map_err(io_context("write", &file_path))?;
+        info!("Created {}", file_path.to_string_lossy());
+        paths.push(file_path);
+    }
+    Ok(paths)
+}
+
+/// add the dependency `name` to the `[dependencies]` table of the content of a `Cargo.toml`,
+/// an existing entry is replaced
+fn add_dependency(
+    cargo_toml: &str,
+    name: &str,
+    dependency: &DependencySpec,
+) -> Result<String, PuzzleError> {
+    let mut doc = cargo_toml
+        .parse::<DocumentMut>()
+        .map_err(|err| PuzzleError::from(err.to_string()))?;
+    let dependency = dependency
+        .to_toml()
+        .parse::<Value>()
+        .map_err(|err| PuzzleError::from(err.to_string()))?;
+    if !doc.contains_key("dependencies") {
+        doc.insert("dependencies", Item::Table(Default::default()));
+    }
+    doc["dependencies"]
+        .as_table_like_mut()
+        .ok_or_else(|| PuzzleError::from("dependencies is not a table"))?
+        .insert(name, Item::Value(dependency));
+    Ok(doc.to_string())
+}
+
+/// set `rust-version` in the `[package]` table of the content of a `Cargo.toml`
+fn set_rust_version(cargo_toml: &str, rust_version: &str) -> Result<String, PuzzleError> {
+    let mut doc = cargo_toml
+        .parse::<DocumentMut>()
+        .map_err(|err| PuzzleError::from(err.to_string()))?;
+    doc.get_mut("package")
+        .and_then(Item::as_table_like_mut)
+        .ok_or_else(|| PuzzleError::from("package is not a table"))?
+        .insert("rust-version", value(rust_version));
+    Ok(doc.to_string())
+}
+
+/// add optional dependencies on `serde` and `serde_json` and a feature `serde` enabling them to
+/// the content of a `Cargo.toml`
+fn add_serde_feature(cargo_toml: &str) -> Result<String, PuzzleError> {
+    let mut doc = cargo_toml
+        .parse::<DocumentMut>()
+        .map_err(|err| PuzzleError::from(err.to_string()))?;
+    for (table, name, entry) in [
+        (
+            "dependencies",
+            "serde",
+            r#"{ version = "1", features = ["derive"], optional = true }"#,
+        ),
+        (
+            "dependencies",
+            "serde_json",
+            r#"{ version = "1", optional = true }"#,
+        ),
+        ("features", "serde", r#"["dep:serde", "dep:serde_json"]"#),
+    ] {
+        let entry = entry
+            .parse::<Value>()
+            .map_err(|err| PuzzleError::from(err.to_string()))?;
+        if !doc.contains_key(table) {
+            doc.insert(table, Item::Table(Default::default()));
+        }
+        doc[table]
+            .as_table_like_mut()
+            .ok_or_else(|| PuzzleError::from(format!("{table} is not a table")))?
+            .insert(name, Item::Value(entry));
+    }
+    Ok(doc.to_string())
+}
+
+/// Check that the crate at `path` compiles by running `cargo check` in its directory
+///
+/// If the check fails, the returned error contains the standard error output of cargo.
+pub fn verify_generated<P: AsRef<Path>>(path: P) -> Result<(), PuzzleError> {
+    let path = path.as_ref();
+    info!("Verifying crate {} ...", path.to_string_lossy());
+
+    let output = Command::new("cargo")
+        .arg("check")
+        .current_dir(path)
+        .output()?;
+    if !output.status.success() {
+        return Err(PuzzleError::from(format!(
+            "'cargo check' failed for '{}':\n{}",
+            path.to_string_lossy(),
+            String::from_utf8_lossy(&output.stderr)
+        )));
+    }
+
+    info!("-> OK");
+    Ok(())
+}
+
+/// format the Rust files among `paths` with `rustfmt` for `edition`, problems are logged as
+/// warnings only
+fn format_generated(paths: &[PathBuf], edition: &str) {
+    let files = paths
+        .iter()
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .collect::<Vec<_>>();
+    if files.is_empty() {
+        return;
+    }
+
+    info!("Formatting {} files ...", files.len());
+    match Command::new("rustfmt")
+        .args(["--edition", edition])
+        .args(&files)
+        .output()
+    {
+        Ok(output) if output.status.success() => info!("-> OK"),
+        Ok(output) => warn!(
+            "Could not format generated files: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ),
+        Err(err) => warn!("Could not run rustfmt: {err}"),
+    }
+}
+
+/// write `content` to file and return the path written to, only log the path
+/// and the content length if `dry_run` is set
+///
+/// If `backup` is set and the file exists with different content, it is renamed to the first
+/// of `{name}.bak`, `{name}.bak.1`, `{name}.bak.2`, ... that does not exist yet.
+fn write_file(
+    content: &str,
+    path: PathBuf,
+    dry_run: bool,
+    backup: bool,
+) -> Result<PathBuf, PuzzleError> {
+    let backup_path = if backup
+        && path.is_file()
+        && fs::read(&path).map_err(io_context("read", &path))? != content.as_bytes()
+    {
+        Some(backup_path(&path))
+    } else {
+        None
+    };
+
+    if dry_run {
+        if let Some(backup_path) = backup_path {
+            info!(
+                "Would move file {} to {}",
+                path.to_string_lossy(),
+                backup_path.to_string_lossy()
+            );
+        }
+        info!(
+            "Would write file {} ({} bytes)",
+            path.to_string_lossy(),
+            content.len()
+        );
+    } else {
+        if let Some(backup_path) = backup_path {
+            info!(
+                "Moving file {} to {} ...",
+                path.to_string_lossy(),
+                backup_path.to_string_lossy()
+            );
+            fs::rename(&path, &backup_path).map_err(io_context("move", &path))?;
+        }
+        info!("Writing file {} ...", path.to_string_lossy());
+        fs::write(&path, content).map_err(io_context("write", &path))?;
+    }
+
+    Ok(path)
+}
+
+/// map an IO error to an error saying which `action` failed on which `path`, the IO error is
+/// kept as source
+fn io_context(action: &'static str, path: &Path) -> impl FnOnce(std::io::Error) -> PuzzleError {
+    let path = path.to_string_lossy().to_string();
+    move |err| PuzzleError::from(err).with_context(format!("Failed to {action} {path}"))
+}
+
+/// get the first path of `{name}.bak`, `{name}.bak.1`, `{name}.bak.2`, ... which does not exist
+fn backup_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    (0..)
+        .map(|n| match n {
+            0 => path.with_file_name(format!("{name}.bak")),
+            n => path.with_file_name(format!("{name}.bak.{n}")),
+        })
+        .find(|backup_path| !backup_path.exists())
+        .unwrap()
+}
+
+pub trait InputProvider {
+    fn load_input(&self, year: u16, day: u16) -> Result<String, PuzzleError>;
+
+    /// load the puzzle description, if the provider supports it
+    ///
+    /// The default implementation returns [`Option::None`].
+    fn load_prompt(&self, _year: u16, _day: u16) -> Result<Option<String>, PuzzleError> {
+        Ok(None)
+    }
+
+    /// open the input as a reader, e.g., to parse a large input line by line with
+    /// [`parse_lines`]
+    ///
+    /// The default implementation wraps the input loaded with [`InputProvider::load_input`].
+    /// Providers reading files stream them instead of reading them into memory.
+    fn open_input(&self, year: u16, day: u16) -> Result<Box<dyn BufRead>, PuzzleError> {
+        Ok(Box::new(Cursor::new(self.load_input(year, day)?)))
+    }
+
+    /// check whether the credentials used to load inputs are valid, e.g., to ask for a new
+    /// session cookie before loading anything
+    ///
+    /// The default implementation returns `Ok(true)` for providers without credentials.
+    fn check_session(&self) -> Result<bool, PuzzleError> {
+        Ok(true)
+    }
+}
+
+/// Parse every line of `reader` with [`FromStr`], see [`InputProvider::open_input`]
+///
+/// Lines are read one at a time, errors name the line number.
+///
+/// # Examples
+/// ```
+/// # use mr_kaffee_aoc::template::parse_lines;
+/// let values = parse_lines::<u32, _>("1\n2\n3\n".as_bytes()).collect::<Result<Vec<_>, _>>();
+/// assert_eq!(vec![1, 2, 3], values.unwrap());
+///
+/// let err = parse_lines::<u32, _>("1\nx\n".as_bytes()).nth(1).unwrap().unwrap_err();
+/// assert!(err.to_string().starts_with("Invalid line 2: "));
+/// ```
+pub fn parse_lines<T, R>(reader: R) -> impl Iterator<Item = Result<T, PuzzleError>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+    R: BufRead,
+{
+    reader.lines().zip(1..).map(|(line, number)| {
+        line?
+            .parse()
+            .map_err(|err| PuzzleError::from(format!("Invalid line {number}: {err}")))
+    })
+}
+
+impl InputProvider for PuzzleIO {
+    fn load_input(&self, year: u16, day: u16) -> Result<String, PuzzleError> {
+        PuzzleIO::load_input(self, year, day)
+    }
+
+    fn load_prompt(&self, year: u16, day: u16) -> Result<Option<String>, PuzzleError> {
+        PuzzleIO::load_prompt(self, year, day).map(Some)
+    }
+
+    fn check_session(&self) -> Result<bool, PuzzleError> {
+        PuzzleIO::check_session(self)
+    }
+}
+
+impl InputProvider for HttpInputProvider {
+    fn load_input(&self, year: u16, day: u16) -> Result<String, PuzzleError> {
+        HttpInputProvider::load_input(self, year, day)
+    }
+}
+
+/// Input provider which persists inputs obtained from an inner provider to disk
+///
+/// Inputs are stored in `{cache_root}/{year}/{day}.txt`. If a cached file exists, it is
+/// returned without calling the inner provider, unless a refresh is forced with
+/// [`CachingInputProvider::with_force_refresh`].
+///
+/// The length and a hash of every input stored are recorded in `{cache_root}/{year}/{day}.meta`.
+/// A forced refresh compares the fresh input against the cached file and only rewrites it if the
+/// input actually changed.
+pub struct CachingInputProvider<'a> {
+    inner: &'a dyn InputProvider,
+    cache_root: PathBuf,
+    force_refresh: bool,
+}
+
+impl<'a> CachingInputProvider<'a> {
+    /// Create a caching provider delegating to `inner` and storing inputs below `cache_root`
+    pub fn new<P: AsRef<Path>>(inner: &'a dyn InputProvider, cache_root: P) -> Self {
+        Self {
+            inner,
+            cache_root: cache_root.as_ref().to_path_buf(),
+            force_refresh: false,
+        }
+    }
+
+    /// Set the force refresh flag
+    ///
+    /// If set, inputs are always loaded from the inner provider. The cached file is only
+    /// rewritten if the input changed, identical inputs are logged as unchanged.
+    pub fn with_force_refresh(self, force_refresh: bool) -> Self {
+        Self {
+            force_refresh,
+            ..self
+        }
+    }
+
+    /// get the path of the cache file for given `year` and `day`
+    pub fn cache_path(&self, year: u16, day: u16) -> PathBuf {
+        self.cache_root
+            .join(year.to_string())
+            .join(format!("{day}.txt"))
+    }
+
+    /// get the path of the metadata file for given `year` and `day`
+    pub fn meta_path(&self, year: u16, day: u16) -> PathBuf {
+        self.cache_path(year, day).with_extension("meta")
+    }
+
+    /// check whether the input for given `year` and `day` is served from the cache without
+    /// calling the inner provider
+    pub fn is_cached(&self, year: u16, day: u16) -> bool {
+        !self.force_refresh && self.cache_path(year, day).is_file()
+    }
+
+    /// delete the cached input and its metadata for given `year` and `day`, if any
+    pub fn invalidate(&self, year: u16, day: u16) -> Result<(), PuzzleError> {
+        for path in [self.cache_path(year, day), self.meta_path(year, day)] {
+            match fs::remove_file(path) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err.into()),
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    /// delete all cached inputs by removing the cache directory, if it exists
+    pub fn clear(&self) -> Result<(), PuzzleError> {
+        match fs::remove_dir_all(&self.cache_root) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// get the sorted days with a cached input for given `year`
+    pub fn cached_days(&self, year: u16) -> Result<Vec<u16>, PuzzleError> {
+        let entries = match fs::read_dir(self.cache_root.join(year.to_string())) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut days = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if !path.is_file() {
+                continue;
+            }
+            if let Some(day) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(".txt"))
+                .and_then(|day| day.parse().ok())
+            {
+                days.push(day);
+            }
+        }
+        days.sort_unstable();
+
+        Ok(days)
+    }
+}
+
+impl<'a> InputProvider for CachingInputProvider<'a> {
+    fn load_input(&self, year: u16, day: u16) -> Result<String, PuzzleError> {
+        let path = self.cache_path(year, day);
+        if path.is_file() && !self.force_refresh {
+            return Ok(fs::read_to_string(path)?);
+        }
+
+        let input = self.inner.load_input(year, day)?;
+        let meta = input_meta(&input);
+        let meta_path = self.meta_path(year, day);
+        if path.is_file() {
+            // compare against the cached file itself, the metadata may be stale or missing
+            if input_meta(&fs::read_to_string(&path)?) == meta {
+                info!("Input for {year}/{day} unchanged");
+                if fs::read_to_string(&meta_path).ok().as_deref() != Some(meta.as_str()) {
+                    fs::write(meta_path, meta)?;
+                }
+                return Ok(input);
+            }
+            info!("Input for {year}/{day} changed, updating the cache");
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, &input)?;
+        fs::write(meta_path, meta)?;
+
+        Ok(input)
+    }
+
+    /// puzzle descriptions are not cached since they change once part 1 is solved
+    fn load_prompt(&self, year: u16, day: u16) -> Result<Option<String>, PuzzleError> {
+        self.inner.load_prompt(year, day)
+    }
+
+    /// cached inputs are streamed from the cache file
+    fn open_input(&self, year: u16, day: u16) -> Result<Box<dyn BufRead>, PuzzleError> {
+        let path = self.cache_path(year, day);
+        if path.is_file() && !self.force_refresh {
+            return Ok(Box::new(BufReader::new(fs::File::open(path)?)));
+        }
+        Ok(Box::new(Cursor::new(self.load_input(year, day)?)))
+    }
+
+    fn check_session(&self) -> Result<bool, PuzzleError> {
+        self.inner.check_session()
+    }
+}
+
+/// metadata of a cached input, its length in bytes and its 64-bit FNV-1a hash
+///
+/// The hash is implemented here since the hashers of the standard library are not guaranteed to
+/// be stable across releases.
+fn input_meta(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("len = {}\nfnv1a = {hash:016x}\n", input.len())
+}
+
+/// Normalize an input: every `\r\n` is replaced by `\n`, then exactly one trailing `\n` is
+/// stripped, if any. No other whitespace is touched.
+///
+/// # Examples
+/// ```
+/// # use mr_kaffee_aoc::template::normalize_input;
+/// assert_eq!("a\nb", normalize_input("a\r\nb\r\n"));
+/// assert_eq!("a\n", normalize_input("a\n\n"));
+/// ```
+pub fn normalize_input(input: &str) -> String {
+    let mut input = input.replace("\r\n", "\n");
+    if input.ends_with('\n') {
+        input.pop();
+    }
+    input
+}
+
+/// Input provider which normalizes the inputs of an inner provider with [`normalize_input`]
+///
+/// Use the inner provider directly for raw inputs.
+pub struct NormalizingInputProvider<'a> {
+    inner: &'a dyn InputProvider,
+}
+
+impl<'a> NormalizingInputProvider<'a> {
+    /// Create a normalizing provider delegating to `inner`
+    pub fn new(inner: &'a dyn InputProvider) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'a> InputProvider for NormalizingInputProvider<'a> {
+    fn load_input(&self, year: u16, day: u16) -> Result<String, PuzzleError> {
+        self.inner
+            .load_input(year, day)
+            .map(|input| normalize_input(&input))
+    }
+
+    fn load_prompt(&self, year: u16, day: u16) -> Result<Option<String>, PuzzleError> {
+        self.inner.load_prompt(year, day)
+    }
+
+    fn check_session(&self) -> Result<bool, PuzzleError> {
+        self.inner.check_session()
+    }
+}
+
+/// Input provider which keeps a minimum interval between the requests of an inner provider,
+/// also across separate process invocations
+///
+/// The time of the last request is stored in the file [`RateLimitedInputProvider::STAMP_FILE`]
+/// in a directory shared by all invocations, typically the cache directory. If the last request
+/// is more recent than the minimum interval, the provider sleeps before the next request. Wrap
+/// it in a [`CachingInputProvider`], so that cached inputs are served without delay.
+///
+/// # Examples
+/// ```
+/// # use mr_kaffee_aoc::{err::PuzzleError, template::{InputProvider, RateLimitedInputProvider}};
+/// # use std::time::{Duration, Instant};
+/// # let dir = std::env::temp_dir().join("mr-kaffee-aoc-rate-limit-doc");
+/// # let _ = std::fs::remove_dir_all(&dir);
+/// # struct Inner;
+/// # impl InputProvider for Inner {
+/// #     fn load_input(&self, _: u16, _: u16) -> Result<String, PuzzleError> {
+/// #         Ok("1\n2\n".to_string())
+/// #     }
+/// # }
+/// # let inner = Inner;
+/// let provider = RateLimitedInputProvider::new(&inner, &dir)
+///     .with_min_interval(Duration::from_millis(50));
+/// let start = Instant::now();
+/// provider.load_input(2022, 1).unwrap();
+/// provider.load_input(2022, 1).unwrap();
+/// assert!(start.elapsed() >= Duration::from_millis(50));
+/// # let _ = std::fs::remove_dir_all(&dir);
+/// ```
+pub struct RateLimitedInputProvider<'a> {
+    inner: &'a dyn InputProvider,
+    stamp_path: PathBuf,
+    min_interval: Duration,
+}
+
+impl<'a> RateLimitedInputProvider<'a> {
+    /// default minimum interval between two requests
+    pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(1);
+
+    /// name of the file storing the time of the last request
+    pub const STAMP_FILE: &'static str = ".last-request";
+
+    /// Create a rate limited provider delegating to `inner` and storing the time of the last
+    /// request in the directory at `dir`
+    pub fn new<P: AsRef<Path>>(inner: &'a dyn InputProvider, dir: P) -> Self {
+        Self {
+            inner,
+            stamp_path: dir.as_ref().join(Self::STAMP_FILE),
+            min_interval: Self::DEFAULT_MIN_INTERVAL,
+        }
+    }
+
+    /// Set the minimum interval between two requests
+    pub fn with_min_interval(self, min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            ..self
+        }
+    }
+
+    /// sleep until the minimum interval since the last request passed and record the time of
+    /// the upcoming request
+    ///
+    /// A missing or unreadable stamp file does not delay the request, problems writing it are
+    /// logged as warnings only.
+    fn wait(&self) {
+        let now = || {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+        };
+        let last = fs::read_to_string(&self.stamp_path)
+            .ok()
+            .and_then(|stamp| stamp.trim().parse::<u64>().ok())
+            .map(Duration::from_millis);
+        if let Some(wait) = last.and_then(|last| (last + self.min_interval).checked_sub(now())) {
+            // a stamp from the future, e.g., after changing the clock, does not block forever
+            let wait = wait.min(self.min_interval);
+            info!("Waiting {wait:?} before the next request");
+            thread::sleep(wait);
+        }
+
+        // round up, so that the interval is never shortened by the truncated milliseconds
+        let stamp = now().as_nanos().div_ceil(1_000_000).to_string();
+        let write = self
+            .stamp_path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&self.stamp_path, stamp));
+        if let Err(err) = write {
+            warn!(
+                "Could not record the request time in {}: {err}",
+                self.stamp_path.to_string_lossy()
+            );
+        }
+    }
+}
+
+impl<'a> InputProvider for RateLimitedInputProvider<'a> {
+    fn load_input(&self, year: u16, day: u16) -> Result<String, PuzzleError> {
+        self.wait();
+        self.inner.load_input(year, day)
+    }
+
+    fn load_prompt(&self, year: u16, day: u16) -> Result<Option<String>, PuzzleError> {
+        self.wait();
+        self.inner.load_prompt(year, day)
+    }
+
+    fn check_session(&self) -> Result<bool, PuzzleError> {
+        self.wait();
+        self.inner.check_session()
+    }
+}
+
+pub fn upd_files<F, S>(path: &Path, config: F, year: u16, day: u16) -> Result<(), PuzzleError>
+where
+    S: AsRef<str>,
+    F: FnOnce() -> S,
+{
+    let vars = build_var_map(config, year, day);
+    let separator = "INCLUDE_PUZZLES";
+
+    let line = render_template(PUZZLE_FACTORY_SNIPPET, &vars);
+    upd_file(separator, &line, path.join("src/main.rs").as_path())?;
+
+    let line = render_template(PUZZLE_INCLUDE_SNIPPET, &vars);
+    upd_file(separator, &line, path.join("Cargo.toml").as_path())?;
+
+    Ok(())
+}
+
+fn upd_file(separator: &str, line: &str, path: &Path) -> Result<bool, PuzzleError> {
+    info!("Updating file {} ...", path.to_string_lossy());
+
+    let re = Regex::new(
+        format!(r"(?ms:(?P<prefix>^.*{separator}:START.*?[\r\n]+)(?P<indent>\s*)(?P<data>.*?{separator}:END)(?P<suffix>.*$))")
+            .as_str(),
+    )
+    .unwrap();
+
+    let s = fs::read_to_string(path)?;
+    if let Some(captures) = re.captures(s.as_str()) {
+        // if regex matches, those groups exist
+        let prefix = captures.name("prefix").unwrap().as_str();
+        let indent = captures.name("indent").unwrap().as_str();
+        let data = captures.name("data").unwrap().as_str();
+        let suffix = captures.name("suffix").unwrap().as_str();
+
+        if !data.contains(line) {
+            let contents = format!("{prefix}{indent}{line}\n{indent}{data}{suffix}");
+            fs::write(path, contents)?;
+            info!("-> Updated");
+            Ok(true)
+        } else {
+            info!("-> Nothing to update");
+            Ok(false)
+        }
+    } else {
+        info!("-> No section to update ({separator}:START ... {separator}:END) found");
+        Ok(false)
+    }
+}
+
+/// Register the crate at `crate_path` as member of the cargo workspace defined by the manifest
+/// at `manifest_path`
+///
+/// The crate is added to `workspace.members` using its path relative to the workspace
+/// directory unless it is already contained. Formatting and comments of the manifest are
+/// preserved. Returns `true` if the manifest was updated.
+pub fn register_in_workspace(manifest_path: &Path, crate_path: &Path) -> Result<bool, PuzzleError> {
+    info!("Updating workspace {} ...", manifest_path.to_string_lossy());
+
+    let workspace_dir = fs::canonicalize(manifest_path)?
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    let member = relative_path(&workspace_dir, &fs::canonicalize(crate_path)?);
+
+    let mut doc = fs::read_to_string(manifest_path)?
+        .parse::<DocumentMut>()
+        .map_err(|err| PuzzleError::from(err.to_string()))?;
+    let workspace = doc
+        .get_mut("workspace")
+        .and_then(Item::as_table_mut)
+        .ok_or_else(|| PuzzleError::from("No [workspace] table found"))?;
+    if !workspace.contains_key("members") {
+        workspace.insert("members", value(Array::new()));
+    }
+    let members = workspace
+        .get_mut("members")
+        .and_then(Item::as_array_mut)
+        .ok_or_else(|| PuzzleError::from("workspace.members is not an array"))?;
+
+    if members.iter().any(|m| m.as_str() == Some(member.as_str())) {
+        info!("-> Nothing to update");
+        return Ok(false);
+    }
+
+    members.push(member.as_str());
+    fs::write(manifest_path, doc.to_string())?;
+    info!("-> Added member {member}");
+
+    Ok(true)
+}
+
+/// check that the content of a generated `Cargo.toml` is valid TOML, the error shows the
+/// offending line
+///
+/// The edition has to be one of [`EDITIONS`] and the `rust-version` a version of up to three
+/// numbers, e.g., `1.70`, otherwise an error of kind [`std::io::ErrorKind::InvalidInput`] is
+/// returned.
+fn validate_cargo_toml(cargo_toml: &str) -> Result<(), PuzzleError> {
+    let doc = cargo_toml.parse::<DocumentMut>().map_err(|err| {
+        PuzzleError::from(format!(
+            "The generated Cargo.toml is invalid, check the templates and variables: {err}"
+        ))
+    })?;
+    let invalid = |message: String| -> PuzzleError {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, message).into()
+    };
+
+    // only string values are validated, `{ workspace = true }` is inherited from the workspace
+    let package = doc.get("package");
+    let string_value = |key: &str| -> Result<Option<&str>, PuzzleError> {
+        match package.and_then(|package| package.get(key)) {
+            None => Ok(None),
+            Some(item) if item.is_str() => Ok(item.as_str()),
+            Some(item)
+                if item
+                    .as_table_like()
+                    .and_then(|table| table.get("workspace"))
+                    .and_then(Item::as_bool)
+                    == Some(true) =>
+            {
+                Ok(None)
+            }
+            Some(item) => Err(invalid(format!(
+                "Invalid {key} '{}' in the generated Cargo.toml, expected a string or \
+                {{ workspace = true }}",
+                item.to_string().trim()
+            ))),
+        }
+    };
+
+    if let Some(edition) = string_value("edition")? {
+        if !EDITIONS.contains(&edition) {
+            return Err(invalid(format!(
+                "Unknown edition '{edition}' in the generated Cargo.toml, expected one of {}",
+                EDITIONS.join(", ")
+            )));
+        }
+    }
+
+    if let Some(rust_version) = string_value("rust-version")? {
+        let parts = rust_version.split('.').collect::<Vec<_>>();
+        if parts.len() > 3
+            || parts
+                .iter()
+                .any(|part| part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()))
+        {
+            return Err(invalid(format!(
+                "Invalid rust-version '{rust_version}' in the generated Cargo.toml, expected a \
+                version like 1.70"
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// get the package edition from the content of a `Cargo.toml`
+fn package_edition(cargo_toml: &str) -> Option<String> {
+    let doc = cargo_toml.parse::<DocumentMut>().ok()?;
+    doc.get("package")?
+        .get("edition")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// get the package name from the content of a `Cargo.toml`
+fn package_name(cargo_toml: &str) -> Option<String> {
+    let doc = cargo_toml.parse::<DocumentMut>().ok()?;
+    doc.get("package")?
+        .get("name")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// find all manifests of packages named `name` below `root`, skipping hidden and `target`
+/// directories
+fn find_package(root: &Path, name: &str) -> Result<Vec<PathBuf>, PuzzleError> {
+    let mut manifests = Vec::new();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let entry_path = entry.path();
+            let file_name = entry.file_name();
+            let file_name = file_name.to_string_lossy();
+            if entry.file_type()?.is_dir() {
+                if !file_name.starts_with('.') && file_name != "target" {
+                    dirs.push(entry_path);
+                }
+            } else if file_name == "Cargo.toml"
+                && fs::read_to_string(&entry_path)
+                    .ok()
+                    .and_then(|content| package_name(&content))
+                    .is_some_and(|package| package == name)
+            {
+                manifests.push(entry_path);
+            }
+        }
+    }
+    Ok(manifests)
+}
+
+/// convert `path` lossily to a string with `/` as separator
+fn slash_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// get the path `to` relative to the directory `from` with `/` as separator, both paths are
+/// expected to be absolute
+fn relative_path(from: &Path, to: &Path) -> String {
+    let from = from.components().collect::<Vec<_>>();
+    let to = to.components().collect::<Vec<_>>();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let parts = from[common..]
+        .iter()
+        .map(|_| "..".to_string())
+        .chain(
+            to[common..]
+                .iter()
+                .map(|c| c.as_os_str().to_string_lossy().to_string()),
+        )
+        .collect::<Vec<_>>();
+
+    if parts.is_empty() {
+        ".".to_string()
+    } else {
+        parts.join("/")
+    }
+}
+
+/// default for the template variables `{RESULT1_TYPE}` and `{RESULT2_TYPE}`
+pub const DEFAULT_RESULT_TYPE: &str = "usize";
+
+/// default for the template variable `{EDITION}`
+pub const DEFAULT_EDITION: &str = "2021";
+
+/// Rust editions accepted in a generated `Cargo.toml`
+pub const EDITIONS: [&str; 4] = ["2015", "2018", "2021", "2024"];
+
+/// minimum length of a downloaded input, shorter inputs are reported by [`write_files_with`]
+pub const MIN_INPUT_LEN: usize = 3;
+
+/// markers of error pages served instead of an input, e.g., if the session is not valid, reported
+/// by [`write_files_with`]
+pub const INPUT_ERROR_MARKERS: [&str; 2] = ["Please log in", "<!DOCTYPE"];
+
+/// default for the template variable `{IGNORED_INPUTS}`, the lines added to `.gitignore` for
+/// the puzzle inputs
+pub const DEFAULT_IGNORED_INPUTS: &str = "input.txt\nexample.txt";
+
+/// value of the template variable `{SERDE_DERIVE}` if [`WriteOptions::serde`] is set, placed
+/// after the `#[derive(Debug)]` of `PuzzleData`
+///
+/// Only `Serialize` is derived, since `PuzzleData` borrows the input as bytes, which cannot be
+/// deserialized from formats like JSON without copying.
+pub const SERDE_DERIVE: &str = "\n    #[cfg_attr(feature = \"serde\", derive(serde::Serialize))]";
+
+const MAIN_RS: &str = include_str!("../templates/_main.rs_");
+const LIB_RS: &str = include_str!("../templates/_lib.rs_");
+const README_ADOC: &str = include_str!("../templates/_README.adoc_");
+const CARGO_TOML: &str = include_str!("../templates/_Cargo.toml_");
+const GITIGNORE: &str = include_str!("../templates/_.gitignore_");
+const BENCH_RS: &str = include_str!("../templates/_bench.rs_");
+const BENCH_CARGO_TOML: &str = include_str!("../templates/_bench_Cargo.toml_");
+const EXAMPLE_RS: &str = include_str!("../templates/_example.rs_");
+const PUZZLE_FACTORY_SNIPPET: &str = include_str!("../templates/_puzzle_factory_snippet_");
+const PUZZLE_INCLUDE_SNIPPET: &str = include_str!("../templates/_puzzle_include_snippet_");
+
+/// Set of templates used by [`write_files`]
+///
+/// Templates may use the variables `{YEAR}`, `{YEAR2}` (two digits), `{YEAR4}` (four digits),
+/// `{DAY}`, `{DAY2}` (two digits), `{AUTHOR}` and `{AUTHOR_EMAIL}` (default to git config
+/// `user.name` and `user.email`), `{AUTHORS}` (quoted entry for the `authors` field in
+/// `Cargo.toml`, empty if no author is known), `{RESULT1_TYPE}` and `{RESULT2_TYPE}` (result
+/// types of the stars, default to [`DEFAULT_RESULT_TYPE`]), `{EXP1}` and `{EXP2}` (expressions
+/// for the expected results of the stars, default to `None`), `{CRATE_DAY}` (the day used in
+/// crate names, defaults to `{DAY}`, set it to `{DAY2}` in the config for zero-padded names
+/// which sort lexicographically), `{EDITION}` (the Rust edition in `Cargo.toml`, defaults to
+/// [`DEFAULT_EDITION`]), `{AOC_DEPENDENCY}` (the dependency on this crate in
+/// `Cargo.toml`, see [`DependencySpec`], defaults to the path `{LIB_DIR}`), `{IGNORED_INPUTS}`
+/// (lines for the puzzle inputs in `.gitignore`, default to [`DEFAULT_IGNORED_INPUTS`]),
+/// `{SERDE_DERIVE}` (attribute deriving serde traits for the parsed input, empty unless
+/// [`WriteOptions::serde`] is set, see [`SERDE_DERIVE`]) as well
+/// as any variable defined in the config passed to [`write_files`], e.g., `{LIB_DIR}` for the
+/// path to this crate. See [`render_template`] for details on the template syntax.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateSet {
+    /// template for `src/main.rs`
+    pub main_rs: String,
+    /// template for `src/lib.rs`
+    pub lib_rs: String,
+    /// template for `Cargo.toml`
+    pub cargo_toml: String,
+    /// template for `.gitignore`
+    pub gitignore: String,
+    /// template for `README.adoc`
+    pub readme_adoc: String,
+    /// template for `benches/bench.rs`
+    pub bench_rs: String,
+    /// template appended to `Cargo.toml` if benchmarks are created
+    pub bench_cargo_toml: String,
+    /// template for `tests/example.rs`, may use `{EXAMPLE_EXP1}` and `{EXAMPLE_EXP2}` for the
+    /// expected results for `example.txt`
+    pub example_rs: String,
+}
+
+impl Default for TemplateSet {
+    /// the built-in templates
+    fn default() -> Self {
+        Self {
+            main_rs: MAIN_RS.to_string(),
+            lib_rs: LIB_RS.to_string(),
+            cargo_toml: CARGO_TOML.to_string(),
+            gitignore: GITIGNORE.to_string(),
+            readme_adoc: README_ADOC.to_string(),
+            bench_rs: BENCH_RS.to_string(),
+            bench_cargo_toml: BENCH_CARGO_TOML.to_string(),
+            example_rs: EXAMPLE_RS.to_string(),
+        }
+    }
+}
+
+impl TemplateSet {
+    /// Load templates from files `main.rs`, `lib.rs`, `Cargo.toml`, `.gitignore`,
+    /// `README.adoc`, `bench.rs`, `bench_Cargo.toml`, and `example.rs` in the directory at `path`. The built-in template is used for any file
+    /// that does not exist.
+    pub fn from_dir<P: AsRef<Path>>(path: P) -> Result<Self, PuzzleError> {
+        let path = path.as_ref();
+        if !path.is_dir() {
+            return Err(PuzzleError::from(format!(
+                "The template directory '{}' does not exist.",
+                path.to_string_lossy()
+            )));
+        }
+
+        let load = |name: &str, default: &str| -> Result<String, PuzzleError> {
+            let file = path.join(name);
+            if file.is_file() {
+                Ok(fs::read_to_string(file)?)
+            } else {
+                Ok(default.to_string())
+            }
+        };
+
+        Ok(Self {
+            main_rs: load("main.rs", MAIN_RS)?,
+            lib_rs: load("lib.rs", LIB_RS)?,
+            cargo_toml: load("Cargo.toml", CARGO_TOML)?,
+            gitignore: load(".gitignore", GITIGNORE)?,
+            readme_adoc: load("README.adoc", README_ADOC)?,
+            bench_rs: load("bench.rs", BENCH_RS)?,
+            bench_cargo_toml: load("bench_Cargo.toml", BENCH_CARGO_TOML)?,
+            example_rs: load("example.rs", EXAMPLE_RS)?,
+        })
+    }
+}
+
+/// Input provider which reads inputs from files on disk
+///
+/// The file is located at `{root}/{pattern}`, where the pattern may contain the variables
+/// `{YEAR}`, `{YEAR2}`, `{YEAR4}`, `{DAY}`, and `{DAY2}`. The default pattern is
+/// [`FileInputProvider::DEFAULT_PATTERN`].
+pub struct FileInputProvider {
+    root: PathBuf,
+    pattern: String,
+}
+
+impl FileInputProvider {
+    /// default pattern for input files below the root directory
+    pub const DEFAULT_PATTERN: &'static str = "{YEAR}/day{DAY}.txt";
+
+    /// Create a file provider reading inputs below `root` using the default pattern
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+            pattern: Self::DEFAULT_PATTERN.to_string(),
+        }
+    }
+
+    /// Set the pattern used to locate input files below the root directory
+    pub fn with_pattern<S: Into<String>>(self, pattern: S) -> Self {
+        Self {
+            pattern: pattern.into(),
+            ..self
+        }
+    }
+
+    /// get the path of the input file for given `year` and `day`
+    pub fn input_path(&self, year: u16, day: u16) -> PathBuf {
+        self.root
+            .join(render_template(&self.pattern, &date_vars(year, day)))
+    }
+}
+
+impl InputProvider for FileInputProvider {
+    fn load_input(&self, year: u16, day: u16) -> Result<String, PuzzleError> {
+        let path = self.input_path(year, day);
+        if !path.is_file() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("No input file found at {}", path.to_string_lossy()),
+            )
+            .into());
+        }
+
+        Ok(fs::read_to_string(path)?)
+    }
+
+    fn open_input(&self, year: u16, day: u16) -> Result<Box<dyn BufRead>, PuzzleError> {
+        let path = self.input_path(year, day);
+        match fs::File::open(&path) {
+            Ok(file) => Ok(Box::new(BufReader::new(file))),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("No input file found at {}", path.to_string_lossy()),
+            )
+            .into()),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/// Input provider which reads the input from standard input, e.g., piped from another command
+/// or pasted into the terminal and finished with Ctrl-D
+///
+/// The year and day requested are ignored unless an expected year and day is set with
+/// [`StdinInputProvider::with_expected`]. Since standard input can be read only once, the
+/// provider supplies the input for a single day.
+#[derive(Debug, Clone, Default)]
+pub struct StdinInputProvider {
+    expected: Option<(u16, u16)>,
+}
+
+impl StdinInputProvider {
+    /// Create a provider accepting any year and day
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the year and day of the input, requests for other days fail
+    pub fn with_expected(self, year: u16, day: u16) -> Self {
+        Self {
+            expected: Some((year, day)),
+        }
+    }
+
+    /// read the input for `year` and `day` from `reader`, an empty input is an error
+    fn read_input<R: Read>(
+        &self,
+        mut reader: R,
+        year: u16,
+        day: u16,
+    ) -> Result<String, PuzzleError> {
+        if let Some((exp_year, exp_day)) = self.expected {
+            if (exp_year, exp_day) != (year, day) {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!(
+                        "The input on standard input is for {exp_year}/{exp_day}, not for {year}/{day}"
+                    ),
+                )
+                .into());
+            }
+        }
+
+        let mut input = String::new();
+        reader
+            .read_to_string(&mut input)
+            .map_err(io_context("read", Path::new("standard input")))?;
+        if input.trim().is_empty() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "No input on standard input, pipe the input or paste it and press Ctrl-D",
+            )
+            .into());
+        }
+
+        Ok(input)
+    }
+}
+
+impl InputProvider for StdinInputProvider {
+    fn load_input(&self, year: u16, day: u16) -> Result<String, PuzzleError> {
+        let stdin = std::io::stdin();
+        if stdin.is_terminal() {
+            info!("Paste the input for {year}/{day} and press Ctrl-D ...");
+        }
+        self.read_input(stdin.lock(), year, day)
+    }
+}
+
+impl dyn InputProvider {
+    /// Create an input provider from a URI
+    ///
+    /// * `file://{root}` reads inputs from files below `root`, see [`FileInputProvider`]
+    /// * `cache://{cache_root}` reads inputs cached below `cache_root` by a
+    ///   [`CachingInputProvider`] without downloading anything
+    /// * `https://{session}@{host}` downloads inputs with a [`PuzzleIO`] using the base URL
+    ///   `https://{host}`, the session is read with [`PuzzleIO::from_env`] if omitted; `http`
+    ///   is accepted for local servers
+    /// * `stdin://` reads the input from standard input, `stdin://{year}/{day}` only accepts
+    ///   requests for the given year and day, see [`StdinInputProvider`]
+    ///
+    /// Other schemes result in an error of kind [`std::io::ErrorKind::InvalidInput`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_aoc::template::InputProvider;
+    /// assert!(<dyn InputProvider>::from_uri("file:///home/me/inputs").is_ok());
+    /// assert!(<dyn InputProvider>::from_uri("cache://.cache/aoc").is_ok());
+    /// assert!(<dyn InputProvider>::from_uri("https://my-session@adventofcode.com").is_ok());
+    /// assert!(<dyn InputProvider>::from_uri("stdin://2022/5").is_ok());
+    /// assert!(<dyn InputProvider>::from_uri("ftp://example.com").is_err());
+    /// ```
+    pub fn from_uri(uri: &str) -> Result<Box<dyn InputProvider>, PuzzleError> {
+        let invalid = |message: String| -> PuzzleError {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, message).into()
+        };
+
+        let (scheme, rest) = uri
+            .split_once("://")
+            .ok_or_else(|| invalid(format!("Invalid input source '{uri}', no scheme")))?;
+        match scheme {
+            "file" => Ok(Box::new(FileInputProvider::new(rest))),
+            "cache" => Ok(Box::new(
+                FileInputProvider::new(rest).with_pattern("{YEAR}/{DAY}.txt"),
+            )),
+            "https" | "http" => {
+                let authority = rest.split('/').next().unwrap_or_default();
+                let (puzzle_io, host_and_path) = match authority.split_once('@') {
+                    Some((session, _)) => (PuzzleIO::from(session), &rest[session.len() + 1..]),
+                    None => (PuzzleIO::from_env()?, rest),
+                };
+                Ok(Box::new(
+                    puzzle_io.with_base_url(format!("{scheme}://{host_and_path}")),
+                ))
+            }
+            "stdin" if rest.is_empty() => Ok(Box::new(StdinInputProvider::new())),
+            "stdin" => {
+                let expected = rest
+                    .split_once('/')
+                    .and_then(|(year, day)| Some((year.parse().ok()?, day.parse().ok()?)));
+                match expected {
+                    Some((year, day)) => Ok(Box::new(
+                        StdinInputProvider::new().with_expected(year, day),
+                    )),
+                    None => Err(invalid(format!(
+                        "Invalid input source '{uri}', expected stdin:// or stdin://{{year}}/{{day}}"
+                    ))),
+                }
+            }
+            _ => Err(invalid(format!(
+                "Unsupported input source '{uri}', expected a file, cache, https, or stdin URI"
+            ))),
+        }
+    }
+}
+
+/// Input provider which tries several providers in order and returns the first input
+/// successfully loaded
+///
+/// If all providers fail, the error messages of the individual providers are aggregated into
+/// a single error.
+pub struct FallbackInputProvider<'a> {
+    providers: Vec<Box<dyn InputProvider + 'a>>,
+}
+
+impl<'a> FallbackInputProvider<'a> {
+    /// Create a fallback provider from providers in the order they are tried
+    pub fn new(providers: Vec<Box<dyn InputProvider + 'a>>) -> Self {
+        Self { providers }
+    }
+}
+
+impl<'a> InputProvider for FallbackInputProvider<'a> {
+    fn load_input(&self, year: u16, day: u16) -> Result<String, PuzzleError> {
+        let mut messages = Vec::new();
+        for provider in &self.providers {
+            match provider.load_input(year, day) {
+                Ok(input) => return Ok(input),
+                Err(err) => messages.push(err.to_string()),
+            }
+        }
+
+        Err(PuzzleError::from(format!(
+            "No input provider succeeded for {year}/{day}: [{}]",
+            messages.join("; ")
+        )))
+    }
+
+    /// get the first puzzle description available, errors of individual providers are ignored
+    fn load_prompt(&self, year: u16, day: u16) -> Result<Option<String>, PuzzleError> {
+        Ok(self
+            .providers
+            .iter()
+            .find_map(|provider| provider.load_prompt(year, day).ok().flatten()))
+    }
+}
+
+/// default number of worker threads used by [`fetch_days`]
+pub const DEFAULT_FETCH_WORKERS: usize = 4;
+
+/// default minimum delay between the start of two requests in [`fetch_days`]
+pub const DEFAULT_FETCH_DELAY: Duration = Duration::from_millis(500);
+
+/// Fetch the inputs for several `days` of a `year` in parallel with
+/// [`DEFAULT_FETCH_WORKERS`] worker threads and at least [`DEFAULT_FETCH_DELAY`] between
+/// requests, see [`fetch_days_with`]
+pub fn fetch_days(
+    provider: &(dyn InputProvider + Sync),
+    year: u16,
+    days: &[u16],
+) -> Vec<(u16, Result<String, PuzzleError>)> {
+    fetch_days_with(
+        provider,
+        year,
+        days,
+        DEFAULT_FETCH_WORKERS,
+        DEFAULT_FETCH_DELAY,
+    )
+}
+
+/// Fetch the inputs for several `days` of a `year` using a pool of `workers` threads
+///
+/// To be polite to the server, the start of two requests is separated by at least `delay`.
+/// The results are returned in the order of `days` regardless of the order of completion.
+/// Progress is reported as in [`refresh_year_with`].
+pub fn fetch_days_with(
+    provider: &(dyn InputProvider + Sync),
+    year: u16,
+    days: &[u16],
+    workers: usize,
+    delay: Duration,
+) -> Vec<(u16, Result<String, PuzzleError>)> {
+    // index of the next day to fetch and earliest start of the next request
+    let state = Mutex::new((0, Instant::now()));
+    let results = Mutex::new((0..days.len()).map(|_| None).collect::<Vec<_>>());
+    let progress = Mutex::new(Progress::new(year, days.len()));
+
+    thread::scope(|scope| {
+        for _ in 0..workers.max(1).min(days.len()) {
+            scope.spawn(|| loop {
+                let (idx, start) = {
+                    let mut state = state.lock().unwrap();
+                    let (idx, start) = *state;
+                    if idx >= days.len() {
+                        break;
+                    }
+                    let start = start.max(Instant::now());
+                    *state = (idx + 1, start + delay);
+                    (idx, start)
+                };
+
+                thread::sleep(start.saturating_duration_since(Instant::now()));
+                progress.lock().unwrap().start(days[idx]);
+                let result = provider
+                    .load_input(year, days[idx])
+                    .map_err(SendableError::from);
+                results.lock().unwrap()[idx] = Some(result);
+                progress.lock().unwrap().advance();
+            });
+        }
+    });
+    progress.into_inner().unwrap().finish();
+
+    days.iter()
+        .copied()
+        .zip(results.into_inner().unwrap())
+        .map(|(day, result)| (day, result.unwrap().map_err(PuzzleError::from)))
+        .collect()
+}
+
+/// interval between two log lines of a [`Progress`] if standard error is not a terminal
+const PROGRESS_LOG_INTERVAL: Duration = Duration::from_secs(5);
+
+/// width of the bar drawn by [`Progress`] in characters
+const PROGRESS_WIDTH: usize = 25;
+
+/// Progress of a bulk fetch with the feature `progress`, drawn as a bar on standard error if it
+/// is a terminal and logged every [`PROGRESS_LOG_INTERVAL`] otherwise
+///
+/// Without the feature, nothing is reported.
+struct Progress {
+    year: u16,
+    total: usize,
+    done: usize,
+    bar: bool,
+    enabled: bool,
+    last_log: Instant,
+}
+
+impl Progress {
+    fn new(year: u16, total: usize) -> Self {
+        let enabled = cfg!(feature = "progress");
+        Self {
+            year,
+            total,
+            done: 0,
+            bar: enabled && std::io::stderr().is_terminal(),
+            enabled,
+            last_log: Instant::now(),
+        }
+    }
+
+    /// render the bar with the day currently fetched, if any
+    fn render(&self, current: Option<u16>) -> String {
+        let filled = (self.done * PROGRESS_WIDTH)
+            .checked_div(self.total)
+            .unwrap_or(PROGRESS_WIDTH);
+        let mut line = format!(
+            "{} [{}{}] {}/{} days",
+            self.year,
+            "#".repeat(filled),
+            ".".repeat(PROGRESS_WIDTH - filled),
+            self.done,
+            self.total
+        );
+        if let Some(day) = current {
+            line.push_str(&format!(", fetching day {day}"));
+        }
+        line
+    }
+
+    /// report that `day` is fetched now
+    fn start(&self, day: u16) {
+        if self.bar {
+            self.draw(Some(day));
+        }
+    }
+
+    /// report that a day completed
+    fn advance(&mut self) {
+        self.done += 1;
+        if self.bar {
+            self.draw(None);
+        } else if self.enabled
+            && (self.done == self.total || self.last_log.elapsed() >= PROGRESS_LOG_INTERVAL)
+        {
+            self.last_log = Instant::now();
+            info!(
+                "Progress: {}/{} days of {}",
+                self.done, self.total, self.year
+            );
+        }
+    }
+
+    /// check whether the bar is drawn, log lines would break it
+    fn is_drawn(&self) -> bool {
+        self.bar
+    }
+
+    /// clear the bar before logging a line, the next update draws it again
+    fn clear(&self) {
+        if self.bar {
+            eprint!("\r\x1b[K");
+        }
+    }
+
+    /// end the line of the bar
+    fn finish(&self) {
+        if self.bar {
+            eprintln!();
+        }
+    }
+
+    fn draw(&self, current: Option<u16>) {
+        // clear the rest of the line, the message may get shorter
+        eprint!("\r{}\x1b[K", self.render(current));
+        let _ = std::io::Write::flush(&mut std::io::stderr());
+    }
+}
+
+/// Fetch the inputs of all unlocked days of a `year` with at least [`DEFAULT_FETCH_DELAY`]
+/// between requests, see [`refresh_year_with`]
+pub fn refresh_year(
+    provider: &dyn InputProvider,
+    year: u16,
+) -> Vec<(u16, Result<String, PuzzleError>)> {
+    refresh_year_with(provider, year, DEFAULT_FETCH_DELAY)
+}
+
+/// Fetch the inputs of days 1 to 25 of a `year` one after the other, the start of two requests
+/// is separated by at least `delay`
+///
+/// Days which are not yet unlocked, i.e., the provider returns an error of kind
+/// [`Kind::Locked`], are skipped. Since puzzles are unlocked in order, no requests are sent for
+/// the days after the first locked day, their results are locked errors as well.
+///
+/// The outcome is logged for every day, followed by the number of days fetched, skipped, and
+/// failed. All requests go through the same `provider`, so a
+/// [`PuzzleIO`](crate::puzzle_io::PuzzleIO) reuses its client for the whole year. Wrap it in a
+/// [`CachingInputProvider`] to fill a cache.
+///
+/// With the feature `progress`, a progress bar replaces the log lines of successful days if
+/// standard error is a terminal. Otherwise, the progress is logged periodically.
+pub fn refresh_year_with(
+    provider: &dyn InputProvider,
+    year: u16,
+    delay: Duration,
+) -> Vec<(u16, Result<String, PuzzleError>)> {
+    let mut results = Vec::new();
+    let mut next_start = Instant::now();
+    let mut locked = false;
+    let mut progress = Progress::new(year, 25);
+    for day in 1..=25 {
+        let result = if locked {
+            Err(PuzzleError::locked(year, day))
+        } else {
+            thread::sleep(next_start.saturating_duration_since(Instant::now()));
+            next_start = Instant::now() + delay;
+            progress.start(day);
+            provider.load_input(year, day)
+        };
+
+        match &result {
+            Ok(_) if progress.is_drawn() => (),
+            Ok(_) => info!("Fetched input for {year}/{day}"),
+            Err(err) if matches!(err.kind(), Kind::Locked(..)) => {
+                locked = true;
+                if !progress.is_drawn() {
+                    info!("Skipped {year}/{day}: {err}");
+                }
+            }
+            Err(err) => {
+                progress.clear();
+                warn!("Failed to fetch input for {year}/{day}: {err}");
+            }
+        }
+        progress.advance();
+        results.push((day, result));
+    }
+
+    progress.finish();
+
+    let fetched = results.iter().filter(|(_, r)| r.is_ok()).count();
+    let skipped = results
+        .iter()
+        .filter(|(_, r)| matches!(r, Err(err) if matches!(err.kind(), Kind::Locked(..))))
+        .count();
+    info!(
+        "Fetched {fetched}, skipped {skipped}, failed {} days of {year}",
+        results.len() - fetched - skipped
+    );
+
+    results
+}
+
+/// representation of a [`PuzzleError`], which is not [`Send`], to pass it between threads
+///
+/// The kinds of IO errors and locked puzzles are preserved, all other errors are reduced to
+/// their message.
+enum SendableError {
+    Io(std::io::ErrorKind, String),
+    Locked(u16, u16),
+    Other(String),
+}
+
+impl From<PuzzleError> for SendableError {
+    fn from(err: PuzzleError) -> Self {
+        match err.kind() {
+            Kind::IoError(source) => Self::Io(source.kind(), source.to_string()),
+            Kind::Locked(year, day) => Self::Locked(*year, *day),
+            _ => Self::Other(err.to_string()),
+        }
+    }
+}
+
+impl From<SendableError> for PuzzleError {
+    fn from(err: SendableError) -> Self {
+        match err {
+            SendableError::Io(kind, message) => std::io::Error::new(kind, message).into(),
+            SendableError::Locked(year, day) => PuzzleError::locked(year, day),
+            SendableError::Other(message) => PuzzleError::from(message),
+        }
+    }
+}
+
+/// build map with date variables `YEAR`, `YEAR2`, `YEAR4`, `DAY`, and `DAY2`
+fn date_vars(year: u16, day: u16) -> HashMap<String, String> {
+    HashMap::from([
+        ("YEAR".to_string(), year.to_string()),
+        ("YEAR2".to_string(), format!("{:02}", year % 100)),
+        (
+            "YEAR4".to_string(),
+            format!("{:04}", if year < 100 { year + 2000 } else { year }),
+        ),
+        ("DAY".to_string(), day.to_string()),
+        ("DAY2".to_string(), format!("{day:02}")),
+    ])
+}
+
+/// build variables map, the function `config` is used to load custom configuration,
+/// the standard use case is `config = read_config`
+fn build_var_map<F, S>(config: F, year: u16, day: u16) -> HashMap<String, String>
+where
+    S: AsRef<str>,
+    F: FnOnce() -> S,
+{
+    let mut vars = date_vars(year, day);
+
+    let mut configs = match serde_json::from_str::<HashMap<String, String>>(config().as_ref()) {
+        Ok(configs) => configs,
+        Err(err) => {
+            warn!("Could not parse config JSON: {err}");
+            HashMap::new()
+        }
+    };
+    for value in configs.values_mut() {
+        *value = render_template(value, &vars);
+    }
+
+    vars.extend(configs);
+
+    // author variables default to git config
+    for (name, key) in [("AUTHOR", "user.name"), ("AUTHOR_EMAIL", "user.email")] {
+        vars.entry(name.to_string())
+            .or_insert_with(|| git_config(key).unwrap_or_default());
+    }
+    let authors = match (vars["AUTHOR"].as_str(), vars["AUTHOR_EMAIL"].as_str()) {
+        ("", _) => String::new(),
+        (author, "") => serde_json::Value::from(author).to_string(),
+        (author, email) => serde_json::Value::from(format!("{author} <{email}>")).to_string(),
+    };
+    vars.entry("AUTHORS".to_string()).or_insert(authors);
+
+    vars.entry("EDITION".to_string())
+        .or_insert_with(|| DEFAULT_EDITION.to_string());
+
+    // result types default to `usize`, expected results to none
+    for name in ["RESULT1_TYPE", "RESULT2_TYPE"] {
+        vars.entry(name.to_string())
+            .or_insert_with(|| DEFAULT_RESULT_TYPE.to_string());
+    }
+    for name in ["EXP1", "EXP2"] {
+        vars.entry(name.to_string())
+            .or_insert_with(|| "None".to_string());
+    }
+
+    // crate names use the day without padding unless configured otherwise, e.g., `{DAY2}`
+    let day = vars["DAY"].clone();
+    vars.entry("CRATE_DAY".to_string()).or_insert(day);
+
+    // puzzle inputs are not to be redistributed, so they are not committed by default
+    vars.entry("IGNORED_INPUTS".to_string())
+        .or_insert_with(|| DEFAULT_IGNORED_INPUTS.to_string());
+
+    // the parsed input only derives serde traits on request
+    vars.entry("SERDE_DERIVE".to_string()).or_default();
+
+    vars
+}
+
+/// Rust expression for an expected result `exp` of type `result_type` used in the `exp` field
+/// of a [`crate::Star`], string results are quoted
+fn exp_expression(exp: &str, result_type: &str) -> String {
+    match result_type {
+        "String" => format!("Some({}.to_string())", serde_json::Value::from(exp)),
+        _ => format!("Some({exp})"),
+    }
+}
+
+/// read a value from git config, if available
+fn git_config(key: &str) -> Option<String> {
+    Command::new("git")
+        .args(["config", key])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|value| value.trim().to_string())
+}
+
+/// Render a template by replacing placeholders `{NAME}` with the values from `vars`
+///
+/// Placeholder names start with an uppercase ASCII letter followed by uppercase ASCII letters,
+/// digits or underscores. Any other braces, e.g., in `format!("{}", x)`, are left untouched.
+/// Use `{{` and `}}` to produce literal `{` and `}`, e.g., `{{YEAR}}` renders to `{YEAR}`.
+/// Placeholders without a value in `vars` are left untouched and a warning is logged.
+///
+/// # Examples
+/// ```
+/// # use mr_kaffee_aoc::template::render_template;
+/// # use std::collections::HashMap;
+/// let vars = HashMap::from([("YEAR".to_string(), "2022".to_string())]);
+/// assert_eq!(
+///     "format!(\"{}\", 2022) {YEAR}",
+///     render_template("format!(\"{}\", {YEAR}) {{YEAR}}", &vars)
+/// );
+/// ```
+pub fn render_template(template: &str, vars: &HashMap<String, String>) -> String {
+    let mut content = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(pos) = rest.find(['{', '}']) {
+        content.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            // escaped brace
+            content.push_str(&rest[..1]);
+            rest = &rest[2..];
+            continue;
+        }
+
+        if let Some(name) = placeholder_name(rest) {
+            match vars.get(name) {
+                Some(value) => content.push_str(value),
+                None => {
+                    warn!("No value for template variable {{{name}}}");
+                    content.push_str(&rest[..name.len() + 2]);
+                }
+            }
+            rest = &rest[name.len() + 2..];
+            continue;
+        }
+
+        content.push_str(&rest[..1]);
+        rest = &rest[1..];
+    }
+    content.push_str(rest);
+
+    content
+}
+
+/// get name of placeholder if `s` starts with a placeholder `{NAME}`
+fn placeholder_name(s: &str) -> Option<&str> {
+    let s = s.strip_prefix('{')?;
+    let len = s
+        .find(|c: char| !(c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_'))
+        .unwrap_or(s.len());
+    let name = &s[..len];
+    match (name.chars().next(), s[len..].starts_with('}')) {
+        (Some(c), true) if c.is_ascii_uppercase() => Some(name),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::fs::remove_dir_all;
+    use std::process::Command;
+    use std::str;
+
+    struct TestInputProvider {}
+
+    impl InputProvider for TestInputProvider {
+        fn load_input(&self, year: u16, day: u16) -> Result<String, PuzzleError> {
+            Ok(format!("Test input for {}/{}\n", year, day))
+        }
+    }
+
+    struct CountingInputProvider {
+        count: Cell<usize>,
+    }
+
+    impl InputProvider for CountingInputProvider {
+        fn load_input(&self, year: u16, day: u16) -> Result<String, PuzzleError> {
+            self.count.set(self.count.get() + 1);
+            Ok(format!("Test input for {}/{}\n", year, day))
+        }
+    }
+
+    #[test]
+    pub fn test_caching_input_provider() {
+        let cache_root = Path::new("target/test_caching_input_provider");
+        let _ = remove_dir_all(cache_root);
+
+        let inner = CountingInputProvider {
+            count: Cell::new(0),
+        };
+        let provider = CachingInputProvider::new(&inner, cache_root);
+
+        let input = provider.load_input(2022, 1).unwrap();
+        assert_eq!("Test input for 2022/1\n", input);
+        assert!(cache_root.join("2022").join("1.txt").is_file());
+
+        let input = provider.load_input(2022, 1).unwrap();
+        assert_eq!("Test input for 2022/1\n", input);
+        assert_eq!(1, inner.count.get(), "inner provider called more than once");
+
+        provider.load_input(2022, 10).unwrap();
+        provider.load_input(2022, 2).unwrap();
+        assert_eq!(vec![1, 2, 10], provider.cached_days(2022).unwrap());
+        assert!(provider.cached_days(2021).unwrap().is_empty());
+
+        provider.invalidate(2022, 1).unwrap();
+        provider.invalidate(2022, 1).unwrap();
+        assert_eq!(vec![2, 10], provider.cached_days(2022).unwrap());
+        provider.load_input(2022, 1).unwrap();
+        assert_eq!(4, inner.count.get(), "invalidated input not loaded again");
+
+        provider.clear().unwrap();
+        provider.clear().unwrap();
+        assert!(!cache_root.exists());
+
+        let _ = remove_dir_all(cache_root);
+    }
+
+    #[test]
+    pub fn test_caching_input_provider_force_refresh() {
+        let cache_root = Path::new("target/test_caching_input_provider_force_refresh");
+        let _ = remove_dir_all(cache_root);
+
+        let inner = CountingInputProvider {
+            count: Cell::new(0),
+        };
+        let provider = CachingInputProvider::new(&inner, cache_root);
+        provider.load_input(2022, 1).unwrap();
+        let meta = fs::read_to_string(provider.meta_path(2022, 1)).unwrap();
+        assert!(meta.starts_with("len = 22\nfnv1a = "), "{meta}");
+
+        assert!(provider.is_cached(2022, 1));
+        assert!(!provider.is_cached(2022, 2));
+
+        // an identical input leaves the cache untouched
+        let provider = provider.with_force_refresh(true);
+        assert!(!provider.is_cached(2022, 1));
+        let input = provider.load_input(2022, 1).unwrap();
+        assert_eq!("Test input for 2022/1\n", input);
+        assert_eq!(2, inner.count.get(), "refresh not forced");
+
+        // a modified cached file is repaired even though the metadata matches the fresh input
+        fs::write(provider.cache_path(2022, 1), "modified").unwrap();
+        provider.load_input(2022, 1).unwrap();
+        assert_eq!(
+            "Test input for 2022/1\n",
+            fs::read_to_string(provider.cache_path(2022, 1)).unwrap()
+        );
+
+        // missing metadata is restored
+        fs::remove_file(provider.meta_path(2022, 1)).unwrap();
+        provider.load_input(2022, 1).unwrap();
+        assert_eq!(
+            "Test input for 2022/1\n",
+            fs::read_to_string(provider.cache_path(2022, 1)).unwrap()
+        );
+        assert_eq!(
+            meta,
+            fs::read_to_string(provider.meta_path(2022, 1)).unwrap()
+        );
+
+        let _ = remove_dir_all(cache_root);
+    }
+
+    #[test]
+    pub fn test_normalizing_input_provider() {
+        let provider = NormalizingInputProvider::new(&TestInputProvider {});
+        assert_eq!(
+            "Test input for 2022/1",
+            provider.load_input(2022, 1).unwrap()
+        );
+
+        assert_eq!("a\nb", normalize_input("a\r\nb"));
+        assert_eq!("a\nb", normalize_input("a\r\nb\r\n"));
+        assert_eq!("a\n\n", normalize_input("a\n\n\n"));
+        assert_eq!("", normalize_input(""));
+    }
+
+    #[test]
+    pub fn test_file_input_provider() {
+        let root = Path::new("target/test_file_input_provider");
+        let _ = remove_dir_all(root);
+        fs::create_dir_all(root.join("2022")).unwrap();
+        fs::write(root.join("2022").join("day3.txt"), "Input 2022/3\n").unwrap();
+
+        let provider = FileInputProvider::new(root);
+        assert_eq!("Input 2022/3\n", provider.load_input(2022, 3).unwrap());
+
+        let result = provider.load_input(2022, 4);
+        assert!(
+            matches!(
+                result.as_ref().map_err(|err| err.kind()),
+                Err(crate::err::Kind::IoError(err)) if err.kind() == std::io::ErrorKind::NotFound
+            ),
+            "Expected a not found error, found: {result:?}"
+        );
+
+        let provider = provider.with_pattern("input_{YEAR2}_{DAY2}.txt");
+        assert_eq!(root.join("input_22_04.txt"), provider.input_path(2022, 4));
+
+        let _ = remove_dir_all(root);
+    }
+
+    #[test]
+    pub fn test_open_input() {
+        let root = Path::new("target/test_open_input");
+        let _ = remove_dir_all(root);
+        fs::create_dir_all(root.join("2022")).unwrap();
+        fs::write(root.join("2022").join("day3.txt"), "1\n2\n3\n").unwrap();
+
+        let provider = FileInputProvider::new(root);
+        let values = parse_lines::<u32, _>(provider.open_input(2022, 3).unwrap());
+        assert_eq!(6, values.map(Result::unwrap).sum::<u32>());
+        assert!(provider.open_input(2022, 4).is_err());
+
+        // the default implementation and the cache
+        let cache_root = root.join("cache");
+        let caching = CachingInputProvider::new(&TestInputProvider {}, &cache_root);
+        for _ in 0..2 {
+            let lines = caching.open_input(2022, 5).unwrap().lines();
+            assert_eq!(
+                vec!["Test input for 2022/5".to_string()],
+                lines.map(Result::unwrap).collect::<Vec<_>>()
+            );
+        }
+        assert!(caching.cache_path(2022, 5).is_file());
+
+        let _ = remove_dir_all(root);
+    }
+
+    struct FailingInputProvider {}
+
+    impl InputProvider for FailingInputProvider {
+        fn load_input(&self, year: u16, day: u16) -> Result<String, PuzzleError> {
+            Err(PuzzleError::from(format!("No input for {year}/{day}")))
+        }
+    }
+
+    #[test]
+    pub fn test_write_files_unwritable() {
+        let root = Path::new("target/test_write_files_unwritable");
+        let _ = remove_dir_all(root);
+        fs::create_dir_all(root).unwrap();
+
+        // a file blocking the `src` directory
+        fs::write(root.join("src"), "not a directory").unwrap();
+        let opts = WriteOptions::new(root).with_force(true);
+        let err = write_files_with(2022, 1, &TestInputProvider {}, &opts).unwrap_err();
+        let expected = format!("Failed to create {}", root.join("src").to_string_lossy());
+        assert!(err.to_string().starts_with(&expected), "{err}");
+        assert!(matches!(err.kind(), Kind::IoError(_)));
+        assert!(std::error::Error::source(&err).is_some());
+        fs::remove_file(root.join("src")).unwrap();
+
+        // a read-only directory, which does not stop privileged users
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            fs::set_permissions(root, fs::Permissions::from_mode(0o555)).unwrap();
+            if fs::write(root.join("probe"), "").is_err() {
+                let err = write_files_with(2022, 1, &TestInputProvider {}, &opts).unwrap_err();
+                assert!(err.to_string().starts_with("Failed to "), "{err}");
+            }
+            fs::set_permissions(root, fs::Permissions::from_mode(0o755)).unwrap();
+        }
+
+        let _ = remove_dir_all(root);
+    }
+
+    #[test]
+    pub fn test_write_files_roll_back() {
+        let root = Path::new("target/test_write_files_roll_back");
+        let _ = remove_dir_all(root);
+        fs::create_dir_all(root).unwrap();
+
+        // nothing is left of a new directory
+        let path = root.join("new").join("day01");
+        let opts = WriteOptions::new(&path);
+        assert!(write_files_with(2022, 1, &FailingInputProvider {}, &opts).is_err());
+        assert!(root.exists());
+        assert!(!root.join("new").exists());
+
+        // pre-existing files and directories are kept
+        fs::write(root.join("keep.txt"), "keep").unwrap();
+        let opts = WriteOptions::new(root).with_force(true);
+        assert!(write_files_with(2022, 1, &FailingInputProvider {}, &opts).is_err());
+        assert!(root.join("keep.txt").exists());
+        assert!(!root.join("src").exists());
+
+        // files overwritten before a later failure are restored, new backups are removed
+        fs::write(root.join("input.txt"), "known good input").unwrap();
+        fs::write(root.join("Cargo.toml"), "original").unwrap();
+        fs::create_dir_all(root.join("src").join("lib.rs")).unwrap();
+        for opts in [opts.clone(), opts.with_backup(true)] {
+            let err = write_files_with(2022, 1, &TestInputProvider {}, &opts).unwrap_err();
+            assert!(err.to_string().contains("lib.rs"), "{err}");
+            assert_eq!(
+                "original",
+                fs::read_to_string(root.join("Cargo.toml")).unwrap()
+            );
+            assert!(!root.join("Cargo.toml.bak").exists());
+            assert!(!root.join(".gitignore").exists());
+        }
+
+        let _ = remove_dir_all(root);
+    }
+
+    #[test]
+    pub fn test_input_provider_from_uri() {
+        let root = Path::new("target/test_input_provider_from_uri");
+        let _ = remove_dir_all(root);
+
+        // reads what a caching provider stored
+        let caching = CachingInputProvider::new(&TestInputProvider {}, root);
+        caching.load_input(2022, 3).unwrap();
+        let provider =
+            <dyn InputProvider>::from_uri("cache://target/test_input_provider_from_uri").unwrap();
+        assert_eq!(
+            "Test input for 2022/3\n",
+            provider.load_input(2022, 3).unwrap()
+        );
+        assert!(provider.load_input(2022, 4).is_err());
+
+        let provider =
+            <dyn InputProvider>::from_uri("file://target/test_input_provider_from_uri").unwrap();
+        fs::write(root.join("2022").join("day3.txt"), "file input").unwrap();
+        assert_eq!("file input", provider.load_input(2022, 3).unwrap());
+
+        for uri in ["ftp://example.com", "no scheme", "stdin://2022"] {
+            let err = <dyn InputProvider>::from_uri(uri).err().unwrap();
+            assert!(
+                matches!(err.kind(), Kind::IoError(err) if err.kind() == std::io::ErrorKind::InvalidInput),
+                "{err}"
+            );
+        }
+
+        let _ = remove_dir_all(root);
+    }
+
+    #[test]
+    pub fn test_rate_limited_input_provider() {
+        let dir = Path::new("target/test_rate_limited_input_provider");
+        let _ = remove_dir_all(dir);
+        let interval = Duration::from_millis(200);
+
+        // separate instances, e.g., in separate processes, share the time of the last request
+        let first = RateLimitedInputProvider::new(&TestInputProvider {}, dir);
+        first.load_input(2022, 1).unwrap();
+        assert!(dir.join(RateLimitedInputProvider::STAMP_FILE).is_file());
+        let start = Instant::now();
+        let second =
+            RateLimitedInputProvider::new(&TestInputProvider {}, dir).with_min_interval(interval);
+        second.load_input(2022, 2).unwrap();
+        assert!(start.elapsed() >= interval / 2, "{:?}", start.elapsed());
+
+        // an unreadable stamp does not delay the request
+        fs::write(dir.join(RateLimitedInputProvider::STAMP_FILE), "garbage").unwrap();
+        let start = Instant::now();
+        second.load_input(2022, 3).unwrap();
+        assert!(start.elapsed() < interval, "{:?}", start.elapsed());
+
+        let _ = remove_dir_all(dir);
+    }
+
+    #[test]
+    pub fn test_stdin_input_provider() {
+        let provider = StdinInputProvider::new();
+        assert_eq!(
+            "piped\n",
+            provider.read_input("piped\n".as_bytes(), 2022, 5).unwrap()
+        );
+        let err = provider.read_input(" \n".as_bytes(), 2022, 5).unwrap_err();
+        assert!(
+            err.to_string().contains("No input on standard input"),
+            "{err}"
+        );
+
+        let provider = provider.with_expected(2022, 5);
+        assert!(provider.read_input("piped".as_bytes(), 2022, 5).is_ok());
+        let err = provider
+            .read_input("piped".as_bytes(), 2022, 6)
+            .unwrap_err();
+        assert!(err.to_string().contains("not for 2022/6"), "{err}");
+    }
+
+    #[test]
+    pub fn test_fallback_input_provider() {
+        let counting = CountingInputProvider {
+            count: Cell::new(0),
+        };
+        let provider = FallbackInputProvider::new(vec![
+            Box::new(FailingInputProvider {}),
+            Box::new(TestInputProvider {}),
+            Box::new(CachingInputProvider::new(&counting, "target/never_used")),
+        ]);
+        assert_eq!(
+            "Test input for 2022/5\n",
+            provider.load_input(2022, 5).unwrap()
+        );
+        assert_eq!(0, counting.count.get(), "provider called after success");
+
+        let provider = FallbackInputProvider::new(vec![
+            Box::new(FailingInputProvider {}),
+            Box::new(FailingInputProvider {}),
+        ]);
+        let message = provider.load_input(2022, 5).err().unwrap().to_string();
+        assert_eq!(
+            "No input provider succeeded for 2022/5: [No input for 2022/5; No input for 2022/5]",
+            message
+        );
+    }
+
+    #[test]
+    pub fn test_write_files_dry_run() {
+        let target_path = Path::new("target/test_write_files_dry_run");
+        let _ = remove_dir_all(target_path);
+        let input_provider = FailingInputProvider {};
+
+        let opts = WriteOptions::new(target_path).with_dry_run(true);
+        let result = write_files_with(2022, 25, &input_provider, &opts);
+        assert_eq!(7, result.unwrap().len());
+        assert!(!target_path.exists(), "dry run created target directory");
+    }
+
+    #[test]
+    pub fn test_write_files_with_benches() {
+        let target_path = Path::new("target/test_write_files_with_benches");
+        let _ = remove_dir_all(target_path);
+
+        let opts = WriteOptions::new(target_path).with_benches(true);
+        let result = write_files_with(2022, 25, &TestInputProvider {}, &opts);
+        assert!(result
+            .unwrap()
+            .contains(&target_path.join("benches").join("bench.rs")));
+
+        let bench_rs = fs::read_to_string(target_path.join("benches").join("bench.rs")).unwrap();
+        assert!(bench_rs.contains("use mr_kaffee_2022_25::"));
+        let cargo_toml = fs::read_to_string(target_path.join("Cargo.toml")).unwrap();
+        let doc = cargo_toml.parse::<DocumentMut>().unwrap();
+        assert!(doc["dev-dependencies"].get("criterion").is_some());
+        assert_eq!(
+            Some("bench"),
+            doc["bench"][0].get("name").and_then(|name| name.as_str())
+        );
+
+        let _ = remove_dir_all(target_path);
+    }
+
+    #[test]
+    pub fn test_write_files_from_existing() {
+        let root = Path::new("target/test_write_files_from_existing");
+        let _ = remove_dir_all(root);
+        let (source_path, target_path) = (root.join("day05"), root.join("day06"));
+        let opts = WriteOptions::new(&source_path)
+            .with_result_types("String", "usize")
+            .with_exp1("ZRL")
+            .with_exp2("(1 + 2) * 3");
+        write_files_with(2022, 5, &TestInputProvider {}, &opts).unwrap();
+        let lib_path = source_path.join("src").join("lib.rs");
+        let lib_rs = fs::read_to_string(&lib_path).unwrap();
+        fs::write(
+            &lib_path,
+            lib_rs + "\n// see https://adventofcode.com/2022/day/5\n",
+        )
+        .unwrap();
+
+        let opts = WriteOptions::new(&target_path);
+        write_files_from_existing(&source_path, 2022, 6, &TestInputProvider {}, &opts).unwrap();
+
+        let lib_rs = fs::read_to_string(target_path.join("src").join("lib.rs")).unwrap();
+        assert!(lib_rs.contains("day: 6,"), "{lib_rs}");
+        assert!(lib_rs.contains("-> String {"), "{lib_rs}");
+        assert!(lib_rs.contains("println!(\"{data:?}\");"), "{lib_rs}");
+        assert!(
+            lib_rs.contains("https://adventofcode.com/2022/day/6\n"),
+            "{lib_rs}"
+        );
+        assert!(!lib_rs.contains("exp: Some"), "{lib_rs}");
+        assert_eq!(2, lib_rs.matches("exp: None,").count(), "{lib_rs}");
+        let main_rs = fs::read_to_string(target_path.join("src").join("main.rs")).unwrap();
+        assert!(main_rs.contains("use mr_kaffee_2022_6::*;"), "{main_rs}");
+        let cargo_toml = fs::read_to_string(target_path.join("Cargo.toml")).unwrap();
+        assert_eq!(
+            Some("mr-kaffee-2022-6".to_string()),
+            package_name(&cargo_toml)
+        );
+        assert!(target_path.join("input.txt").is_file());
+
+        // the source has to be a day crate
+        fs::write(
+            source_path.join("Cargo.toml"),
+            "[package]\nname = \"other\"\n",
+        )
+        .unwrap();
+        let opts = WriteOptions::new(root.join("day07"));
+        let err = write_files_from_existing(&source_path, 2022, 7, &TestInputProvider {}, &opts)
+            .unwrap_err();
+        assert!(err.to_string().contains("is not a day crate"), "{err}");
+        assert!(!root.join("day07").exists());
+
+        let _ = remove_dir_all(root);
+    }
+
+    #[test]
+    pub fn test_write_files_part_two_locked() {
+        struct PromptInputProvider {
+            prompt: Cell<&'static str>,
+        }
+
+        impl InputProvider for PromptInputProvider {
+            fn load_input(&self, _year: u16, _day: u16) -> Result<String, PuzzleError> {
+                Ok("test input with enough content\n".to_string())
+            }
+
+            fn load_prompt(&self, _year: u16, _day: u16) -> Result<Option<String>, PuzzleError> {
+                Ok(Some(self.prompt.get().to_string()))
+            }
+        }
+
+        let target_path = Path::new("target/test_write_files_part_two_locked");
+        let _ = remove_dir_all(target_path);
+        let provider = PromptInputProvider {
+            prompt: Cell::new("## --- Day 25 ---\n\nPart 1"),
+        };
+        let opts = WriteOptions::new(target_path).with_ensure(true);
+        write_files_with(2022, 25, &provider, &opts).unwrap();
+        let readme = fs::read_to_string(target_path.join("README.md")).unwrap();
+        assert_eq!(
+            format!("## --- Day 25 ---\n\nPart 1{PART_TWO_LOCKED_NOTE}"),
+            readme
+        );
+
+        // ensure mode leaves the existing description untouched
+        provider
+            .prompt
+            .set("## --- Day 25 ---\n\nPart 1\n\n## --- Part Two ---\n\nPart 2");
+        let paths = write_files_with(2022, 25, &provider, &opts).unwrap();
+        assert!(paths.is_empty(), "{paths:?}");
+        assert_eq!(
+            readme,
+            fs::read_to_string(target_path.join("README.md")).unwrap()
+        );
+
+        // the incomplete description is replaced on request, but not once it is complete
+        let opts = opts.with_refresh_prompt(true);
+        write_files_with(2022, 25, &provider, &opts).unwrap();
+        let readme = fs::read_to_string(target_path.join("README.md")).unwrap();
+        assert!(readme.ends_with("Part 2"), "{readme}");
+        provider.prompt.set("something else");
+        write_files_with(2022, 25, &provider, &opts).unwrap();
+        assert_eq!(
+            readme,
+            fs::read_to_string(target_path.join("README.md")).unwrap()
+        );
+
+        let _ = remove_dir_all(target_path);
+    }
+
+    #[test]
+    pub fn test_write_files_edition() {
+        let target_path = Path::new("target/test_write_files_edition");
+        let _ = remove_dir_all(target_path);
+
+        let opts = WriteOptions::new(target_path);
+        write_files_with(2022, 25, &TestInputProvider {}, &opts).unwrap();
+        let doc = fs::read_to_string(target_path.join("Cargo.toml"))
+            .unwrap()
+            .parse::<DocumentMut>()
+            .unwrap();
+        assert_eq!(Some(DEFAULT_EDITION), doc["package"]["edition"].as_str());
+        assert!(doc["package"].get("rust-version").is_none());
+
+        let opts = opts
+            .with_force(true)
+            .with_edition("2024")
+            .with_rust_version("1.85");
+        write_files_with(2022, 25, &TestInputProvider {}, &opts).unwrap();
+        let doc = fs::read_to_string(target_path.join("Cargo.toml"))
+            .unwrap()
+            .parse::<DocumentMut>()
+            .unwrap();
+        assert_eq!(Some("2024"), doc["package"]["edition"].as_str());
+        assert_eq!(Some("1.85"), doc["package"]["rust-version"].as_str());
+
+        let err = write_files_with(
+            2022,
+            25,
+            &TestInputProvider {},
+            &opts.clone().with_edition("2022"),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Unknown edition '2022'"), "{err}");
+        let err = write_files_with(
+            2022,
+            25,
+            &TestInputProvider {},
+            &opts.with_rust_version("1.x"),
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("Invalid rust-version '1.x'"),
+            "{err}"
+        );
+
+        let _ = remove_dir_all(target_path);
+    }
+
+    #[test]
+    pub fn test_validate_cargo_toml() {
+        validate_cargo_toml("[package]\nedition = \"2021\"\nrust-version = \"1.70\"\n").unwrap();
+        validate_cargo_toml("[package]\nname = \"day01\"\n").unwrap();
+        validate_cargo_toml(
+            "[package]\nedition.workspace = true\nrust-version = { workspace = true }\n",
+        )
+        .unwrap();
+
+        let err = validate_cargo_toml("[package]\nedition = 2021\n").unwrap_err();
+        assert!(err.to_string().contains("Invalid edition '2021'"), "{err}");
+        let err =
+            validate_cargo_toml("[package]\nrust-version = { workspace = false }\n").unwrap_err();
+        assert!(err.to_string().contains("Invalid rust-version"), "{err}");
+    }
+
+    #[test]
+    pub fn test_write_files_target_is_file() {
+        let target_path = Path::new("target/test_write_files_target_is_file");
+        let _ = remove_dir_all(target_path);
+        fs::create_dir_all(target_path).unwrap();
+        let file_path = target_path.join("day01");
+        fs::write(&file_path, "not a directory").unwrap();
+
+        let opts = WriteOptions::new(&file_path).with_force(true);
+        let err = write_files_with(2022, 1, &TestInputProvider {}, &opts).unwrap_err();
+        assert!(
+            matches!(err.kind(), Kind::IoError(err) if err.kind() == std::io::ErrorKind::InvalidInput),
+            "{err:?}"
+        );
+        assert!(err.to_string().contains("is a file"), "{err}");
+        assert_eq!("not a directory", fs::read_to_string(&file_path).unwrap());
+
+        let _ = remove_dir_all(target_path);
+    }
+
+    #[test]
+    pub fn test_write_files_serde() {
+        let target_path = Path::new("target/test_write_files_serde");
+        for serde in [false, true] {
+            let _ = remove_dir_all(target_path);
+            let opts = WriteOptions::new(target_path)
+                .with_line_ending(LineEnding::Lf)
+                .with_serde(serde);
+            write_files_with(2022, 25, &TestInputProvider {}, &opts).unwrap();
+
+            let lib_rs = fs::read_to_string(target_path.join("src").join("lib.rs")).unwrap();
+            assert_eq!(serde, lib_rs.contains(SERDE_DERIVE), "{lib_rs}");
+            assert!(lib_rs.contains("#[derive(Debug)]"), "{lib_rs}");
+            let cargo_toml = fs::read_to_string(target_path.join("Cargo.toml")).unwrap();
+            let doc = cargo_toml.parse::<DocumentMut>().unwrap();
+            assert_eq!(serde, doc["dependencies"].get("serde").is_some());
+            assert_eq!(serde, doc.get("features").is_some());
+            if serde {
+                assert_eq!(
+                    Some(true),
+                    doc["dependencies"]["serde_json"]["optional"].as_bool()
+                );
+                assert_eq!(2, doc["features"]["serde"].as_array().unwrap().len());
+            }
+        }
+
+        // the generated crate compiles with the feature enabled, if serde is available offline
+        let check = |args: &[&str]| {
+            Command::new("cargo")
+                .args(args)
+                .current_dir(target_path)
+                .output()
+                .unwrap()
+        };
+        let opts = WriteOptions::new(target_path)
+            .with_force(true)
+            .with_lib_path(env!("CARGO_MANIFEST_DIR"))
+            .with_serde(true);
+        write_files_with(2022, 25, &TestInputProvider {}, &opts).unwrap();
+        if check(&["fetch", "--offline"]).status.success() {
+            let output = check(&["check", "--offline", "--features", "serde"]);
+            assert!(
+                output.status.success(),
+                "{}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        let _ = remove_dir_all(target_path);
+    }
+
+    #[test]
+    pub fn test_write_files_example_test() {
+        let target_path = Path::new("target/test_write_files_example_test");
+        let _ = remove_dir_all(target_path);
+
+        let opts = WriteOptions::new(target_path).with_example_test("0", "0");
+        assert!(opts.manifest().contains(&"tests/example.rs"));
+        let paths = write_files_with(2022, 25, &TestInputProvider {}, &opts).unwrap();
+        assert!(paths.contains(&target_path.join("tests").join("example.rs")));
+
+        let example_rs = fs::read_to_string(target_path.join("tests").join("example.rs")).unwrap();
+        assert!(
+            example_rs.contains("use mr_kaffee_2022_25::puzzle;"),
+            "{example_rs}"
+        );
+        assert!(
+            example_rs.contains(".assert_example(EXAMPLE, Some(0), Some(0))"),
+            "{example_rs}"
+        );
+
+        let _ = remove_dir_all(target_path);
+    }
+
+    #[test]
+    pub fn test_manifest() {
+        let target_path = Path::new("target/test_manifest");
+        for with_benches in [false, true] {
+            let _ = remove_dir_all(target_path);
+            let opts = WriteOptions::new(target_path).with_benches(with_benches);
+            let paths = write_files_with(2022, 25, &TestInputProvider {}, &opts).unwrap();
+
+            // the test input provider does not supply a puzzle description
+            let manifest = opts
+                .manifest()
+                .into_iter()
+                .filter(|file| *file != "README.md")
+                .map(|file| target_path.join(file))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                manifest.iter().map(|p| slash_path(p)).collect::<Vec<_>>(),
+                paths.iter().map(|p| slash_path(p)).collect::<Vec<_>>()
+            );
+        }
+
+        let _ = remove_dir_all(target_path);
     }
-    content
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs::remove_dir_all;
-    use std::process::Command;
-    use std::str;
+    #[test]
+    pub fn test_write_files_keep_input() {
+        let target_path = Path::new("target/test_write_files_keep_input");
+        let _ = remove_dir_all(target_path);
+        fs::create_dir_all(target_path).unwrap();
+        fs::write(target_path.join("input.txt"), "known good input").unwrap();
 
-    struct TestInputProvider {}
+        // failing input provider must not be called
+        let opts = WriteOptions::new(target_path).with_force(true);
+        let result = write_files_with(2022, 25, &FailingInputProvider {}, &opts);
+        assert!(!result.unwrap().contains(&target_path.join("input.txt")));
+        assert_eq!(
+            "known good input",
+            fs::read_to_string(target_path.join("input.txt")).unwrap()
+        );
 
-    impl InputProvider for TestInputProvider {
-        fn load_input(&self, year: u16, day: u16) -> Result<String, PuzzleError> {
-            Ok(format!("Test input for {}/{}\n", year, day))
-        }
+        // input is replaced if refreshed
+        let opts = opts.with_refresh_input(true);
+        let result = write_files_with(2022, 25, &TestInputProvider {}, &opts);
+        assert!(result.unwrap().contains(&target_path.join("input.txt")));
+        assert_eq!(
+            "Test input for 2022/25\n",
+            fs::read_to_string(target_path.join("input.txt")).unwrap()
+        );
+
+        let _ = remove_dir_all(target_path);
     }
 
     /// create test files and execute tests and program with cargo
@@ -192,6 +3744,18 @@ mod tests {
         // write files
         let result = write_files(target_path, &input_provider, config, year, day, force);
         assert!(matches!(result, Ok(_)));
+        assert_eq!(
+            vec![
+                target_path.join("input.txt"),
+                target_path.join("example.txt"),
+                target_path.join(".gitignore"),
+                target_path.join("Cargo.toml"),
+                target_path.join("README.adoc"),
+                target_path.join("src").join("main.rs"),
+                target_path.join("src").join("lib.rs"),
+            ],
+            result.unwrap()
+        );
 
         // run tests using `cargo test`
         let result = Command::new("cargo")
@@ -239,6 +3803,681 @@ mod tests {
         let _ = remove_dir_all(target_path);
     }
 
+    #[test]
+    pub fn test_template_set_from_dir() {
+        let template_path = Path::new("target/test_template_set_from_dir");
+        let _ = remove_dir_all(template_path);
+        fs::create_dir_all(template_path).unwrap();
+        fs::write(template_path.join("lib.rs"), "// my lib for {YEAR}/{DAY}\n").unwrap();
+
+        let templates = TemplateSet::from_dir(template_path).unwrap();
+        assert_eq!("// my lib for {YEAR}/{DAY}\n", templates.lib_rs);
+        assert_eq!(MAIN_RS, templates.main_rs);
+        assert_eq!(CARGO_TOML, templates.cargo_toml);
+
+        let _ = remove_dir_all(template_path);
+        assert!(TemplateSet::from_dir(template_path).is_err());
+    }
+
+    #[test]
+    pub fn test_write_files_result_types() {
+        let target_path = Path::new("target/test_write_files_result_types");
+        let _ = remove_dir_all(target_path);
+
+        let opts = WriteOptions::new(target_path).with_result_types("u64", "String");
+        write_files_with(2022, 25, &TestInputProvider {}, &opts).unwrap();
+
+        let lib_rs = fs::read_to_string(target_path.join("src").join("lib.rs")).unwrap();
+        assert!(lib_rs.contains("Puzzle<'static, PuzzleData<'static>, u64, u64, String, String>"));
+        assert!(lib_rs.contains("pub fn star_1(data: &PuzzleData) -> u64 {"));
+        assert!(lib_rs.contains("pub fn star_2(data: &PuzzleData) -> String {"));
+        assert_eq!(2, lib_rs.matches("exp: None,").count());
+
+        let opts = opts.with_force(true).with_exp1("42").with_exp2("ABC");
+        write_files_with(2022, 25, &TestInputProvider {}, &opts).unwrap();
+
+        let lib_rs = fs::read_to_string(target_path.join("src").join("lib.rs")).unwrap();
+        assert!(lib_rs.contains("exp: Some(42),"));
+        assert!(lib_rs.contains("exp: Some(\"ABC\".to_string()),"));
+
+        let err = write_files_with(
+            2022,
+            25,
+            &TestInputProvider {},
+            &opts.with_result_types("usize", "&str"),
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("Unsupported result type '&str'"),
+            "{err}"
+        );
+
+        let _ = remove_dir_all(target_path);
+    }
+
+    #[test]
+    pub fn test_fetch_days() {
+        let delay = Duration::from_millis(20);
+        let timer = Instant::now();
+        let results = fetch_days_with(&TestInputProvider {}, 2022, &[3, 1, 2], 2, delay);
+        assert!(timer.elapsed() >= delay * 2, "requests not delayed");
+        assert_eq!(
+            vec![
+                (3, "Test input for 2022/3\n".to_string()),
+                (1, "Test input for 2022/1\n".to_string()),
+                (2, "Test input for 2022/2\n".to_string()),
+            ],
+            results
+                .into_iter()
+                .map(|(day, result)| (day, result.unwrap()))
+                .collect::<Vec<_>>()
+        );
+
+        let results = fetch_days(&FailingInputProvider {}, 2022, &[1]);
+        assert_eq!(1, results.len());
+        assert!(results[0].1.is_err());
+
+        assert!(fetch_days(&TestInputProvider {}, 2022, &[]).is_empty());
+    }
+
+    #[test]
+    pub fn test_progress_render() {
+        let mut progress = Progress::new(2022, 25);
+        assert_eq!(
+            format!("2022 [{}] 0/25 days, fetching day 1", ".".repeat(25)),
+            progress.render(Some(1))
+        );
+        for _ in 0..10 {
+            progress.advance();
+        }
+        assert_eq!(
+            format!("2022 [{}{}] 10/25 days", "#".repeat(10), ".".repeat(15)),
+            progress.render(None)
+        );
+        assert_eq!(
+            format!("2022 [{}] 0/0 days", "#".repeat(25)),
+            Progress::new(2022, 0).render(None)
+        );
+    }
+
+    struct LockingInputProvider {
+        unlocked: u16,
+        count: Cell<usize>,
+    }
+
+    impl InputProvider for LockingInputProvider {
+        fn load_input(&self, year: u16, day: u16) -> Result<String, PuzzleError> {
+            self.count.set(self.count.get() + 1);
+            match day {
+                2 => Err(PuzzleError::from("server error")),
+                day if day > self.unlocked => Err(PuzzleError::locked(year, day)),
+                _ => Ok(format!("Test input for {}/{}\n", year, day)),
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_refresh_year() {
+        let provider = LockingInputProvider {
+            unlocked: 3,
+            count: Cell::new(0),
+        };
+        let delay = Duration::from_millis(10);
+        let timer = Instant::now();
+        let results = refresh_year_with(&provider, 2022, delay);
+        assert!(timer.elapsed() >= delay * 3, "requests not delayed");
+
+        // no requests after the first locked day
+        assert_eq!(4, provider.count.get());
+        assert_eq!(
+            (1..=25).collect::<Vec<_>>(),
+            results.iter().map(|(day, _)| *day).collect::<Vec<_>>()
+        );
+        assert_eq!("Test input for 2022/1\n", results[0].1.as_ref().unwrap());
+        assert!(matches!(
+            results[1].1.as_ref().unwrap_err().kind(),
+            Kind::Other
+        ));
+        assert!(results[2].1.is_ok());
+        assert!(results[3..]
+            .iter()
+            .all(|(_, r)| matches!(r.as_ref().unwrap_err().kind(), Kind::Locked(2022, _))));
+    }
+
+    #[test]
+    pub fn test_write_files_dependency() {
+        let target_path = Path::new("target/test_write_files_dependency");
+        let _ = remove_dir_all(target_path);
+
+        let git = DependencySpec::Git {
+            url: "https://example.com/aoc.git".to_string(),
+            rev: None,
+        };
+        let opts = WriteOptions::new(target_path)
+            .with_lib_path("../aoc")
+            .with_dependency(git);
+        write_files_with(2022, 25, &TestInputProvider {}, &opts).unwrap();
+        let cargo_toml = fs::read_to_string(target_path.join("Cargo.toml")).unwrap();
+        let doc = cargo_toml.parse::<DocumentMut>().unwrap();
+        let dependency = &doc["dependencies"]["mr-kaffee-aoc"];
+        assert_eq!(
+            Some("https://example.com/aoc.git"),
+            dependency.get("git").and_then(|git| git.as_str())
+        );
+        assert!(dependency.get("path").is_none());
+
+        let opts = opts
+            .with_force(true)
+            .with_dependency(DependencySpec::Version("0.2".to_string()));
+        write_files_with(2022, 25, &TestInputProvider {}, &opts).unwrap();
+        let cargo_toml = fs::read_to_string(target_path.join("Cargo.toml")).unwrap();
+        let doc = cargo_toml.parse::<DocumentMut>().unwrap();
+        assert_eq!(Some("0.2"), doc["dependencies"]["mr-kaffee-aoc"].as_str());
+
+        let _ = remove_dir_all(target_path);
+    }
+
+    #[test]
+    pub fn test_write_files_lib_path() {
+        let target_path = Path::new("target/test_write_files_lib_path");
+        let _ = remove_dir_all(target_path);
+
+        for (lib_path, exp) in [
+            ("../my aoc/lib", "../my aoc/lib"),
+            ("..\\my aoc\\lib", "../my aoc/lib"),
+        ] {
+            let opts = WriteOptions::new(target_path)
+                .with_force(true)
+                .with_lib_path(lib_path)
+                .with_line_ending(LineEnding::CrLf);
+            write_files_with(2022, 25, &TestInputProvider {}, &opts).unwrap();
+
+            let cargo_toml = fs::read_to_string(target_path.join("Cargo.toml")).unwrap();
+            assert!(cargo_toml.contains("\r\n"));
+            assert!(!cargo_toml.replace("\r\n", "").contains('\n'));
+            let doc = cargo_toml.parse::<DocumentMut>().unwrap();
+            assert_eq!(
+                Some(exp),
+                doc["dependencies"]["mr-kaffee-aoc"]
+                    .get("path")
+                    .and_then(|path| path.as_str())
+            );
+        }
+
+        // inputs are not converted
+        assert_eq!(
+            "Test input for 2022/25\n",
+            fs::read_to_string(target_path.join("input.txt")).unwrap()
+        );
+
+        let _ = remove_dir_all(target_path);
+    }
+
+    #[test]
+    pub fn test_write_files_gitignore() {
+        let target_path = Path::new("target/test_write_files_gitignore");
+        let _ = remove_dir_all(target_path);
+        let opts = WriteOptions::new(target_path).with_line_ending(LineEnding::Lf);
+
+        write_files_with(2022, 25, &TestInputProvider {}, &opts).unwrap();
+        let gitignore = fs::read_to_string(target_path.join(".gitignore")).unwrap();
+        assert_eq!(
+            vec!["**/target", "input.txt", "example.txt"],
+            gitignore.lines().collect::<Vec<_>>()
+        );
+
+        let opts = opts.with_force(true).with_commit_inputs(true);
+        write_files_with(2022, 25, &TestInputProvider {}, &opts).unwrap();
+        let gitignore = fs::read_to_string(target_path.join(".gitignore")).unwrap();
+        assert!(!gitignore.contains("input.txt"));
+        assert!(gitignore.contains("**/target"));
+
+        let _ = remove_dir_all(target_path);
+    }
+
+    struct TinyInputProvider {}
+
+    impl InputProvider for TinyInputProvider {
+        fn load_input(&self, _year: u16, _day: u16) -> Result<String, PuzzleError> {
+            Ok("\n".to_string())
+        }
+    }
+
+    struct ErrorPageInputProvider {}
+
+    impl InputProvider for ErrorPageInputProvider {
+        fn load_input(&self, _year: u16, _day: u16) -> Result<String, PuzzleError> {
+            Ok("Puzzle inputs differ by user.  Please log in to get your puzzle input.\n".into())
+        }
+    }
+
+    #[test]
+    pub fn test_write_files_tiny_input() {
+        let target_path = Path::new("target/test_write_files_tiny_input");
+        let _ = remove_dir_all(target_path);
+
+        // warning only
+        let opts = WriteOptions::new(target_path);
+        write_files_with(2022, 25, &TinyInputProvider {}, &opts).unwrap();
+        assert_eq!(
+            "\n",
+            fs::read_to_string(target_path.join("input.txt")).unwrap()
+        );
+
+        let opts = opts
+            .with_force(true)
+            .with_refresh_input(true)
+            .with_strict_input(true);
+        let err = write_files_with(2022, 25, &TinyInputProvider {}, &opts).unwrap_err();
+        assert!(
+            err.to_string().contains("2022/25 has only 0 bytes"),
+            "{err}"
+        );
+
+        let err = write_files_with(2022, 25, &ErrorPageInputProvider {}, &opts).unwrap_err();
+        assert!(err.to_string().contains("'Please log in'"), "{err}");
+
+        let _ = remove_dir_all(target_path);
+    }
+
+    #[test]
+    pub fn test_write_files_crate_day() {
+        let target_path = Path::new("target/test_write_files_crate_day");
+        let _ = remove_dir_all(target_path);
+        let opts = WriteOptions::new(target_path).with_config(r#"{"CRATE_DAY": "{DAY2}"}"#);
+        write_files_with(2022, 5, &TestInputProvider {}, &opts).unwrap();
+
+        let cargo_toml = fs::read_to_string(target_path.join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("name = \"mr-kaffee-2022-05\""));
+        let main_rs = fs::read_to_string(target_path.join("src").join("main.rs")).unwrap();
+        assert!(main_rs.contains("use mr_kaffee_2022_05::*;"));
+        let lib_rs = fs::read_to_string(target_path.join("src").join("lib.rs")).unwrap();
+        assert!(lib_rs.contains("day: 5,"));
+
+        let _ = remove_dir_all(target_path);
+    }
+
+    #[test]
+    pub fn test_write_files_invalid_cargo_toml() {
+        let target_path = Path::new("target/test_write_files_invalid_cargo_toml");
+        let _ = remove_dir_all(target_path);
+        let templates = TemplateSet {
+            cargo_toml: "[package]\nname = \"day-{DAY}\"\nauthors = [{AUTHORZ}]\n".to_string(),
+            ..TemplateSet::default()
+        };
+        let opts = WriteOptions::new(target_path).with_template_set(templates);
+
+        let err = write_files_with(2022, 25, &TestInputProvider {}, &opts).unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("Cargo.toml is invalid"), "{msg}");
+        assert!(msg.contains("authors = [{AUTHORZ}]"), "{msg}");
+        assert!(!target_path.exists());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    pub fn test_write_files_post_hook() {
+        let target_path = Path::new("target/test_write_files_post_hook");
+        let _ = remove_dir_all(target_path);
+        let opts = WriteOptions::new(target_path)
+            .with_post_hook(PostHook::shell("echo $AOC_YEAR/$AOC_DAY > hook.txt"));
+        write_files_with(2022, 25, &TestInputProvider {}, &opts).unwrap();
+        assert_eq!(
+            "2022/25\n",
+            fs::read_to_string(target_path.join("hook.txt")).unwrap()
+        );
+
+        let opts = opts
+            .with_force(true)
+            .with_post_hook(PostHook::shell("echo oops >&2; exit 3"));
+        let err = write_files_with(2022, 25, &TestInputProvider {}, &opts).unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("Post hook 'sh -c echo oops"), "{msg}");
+        assert!(msg.contains("oops\n"), "{msg}");
+        assert!(target_path.join("src").join("lib.rs").exists());
+
+        let _ = remove_dir_all(target_path);
+    }
+
+    #[test]
+    pub fn test_write_files_format() {
+        let target_path = Path::new("target/test_write_files_format");
+        let _ = remove_dir_all(target_path);
+        let templates = TemplateSet {
+            main_rs: "fn   main( )  {  println!(\"{YEAR}\") ; }".to_string(),
+            ..TemplateSet::default()
+        };
+        let opts = WriteOptions::new(target_path)
+            .with_template_set(templates)
+            .with_line_ending(LineEnding::Lf)
+            .with_format(true);
+        write_files_with(2022, 25, &TestInputProvider {}, &opts).unwrap();
+
+        // files are left unformatted if rustfmt is not available
+        let expected = if Command::new("rustfmt").arg("--version").output().is_ok() {
+            "fn main() {\n    println!(\"2022\");\n}\n"
+        } else {
+            "fn   main( )  {  println!(\"2022\") ; }"
+        };
+        assert_eq!(
+            expected,
+            fs::read_to_string(target_path.join("src").join("main.rs")).unwrap()
+        );
+
+        let _ = remove_dir_all(target_path);
+    }
+
+    #[test]
+    pub fn test_write_files_range() {
+        let root = Path::new("target/test_write_files_range");
+        let _ = remove_dir_all(root);
+        let provider = LockingInputProvider {
+            unlocked: 3,
+            count: Cell::new(0),
+        };
+
+        let opts = WriteOptions::new(root.join("day{DAY2}"));
+        let results = write_files_range(&provider, 2022, 1..=4, &opts);
+        assert_eq!(
+            vec![1, 2, 3, 4],
+            results.iter().map(|(day, _)| *day).collect::<Vec<_>>()
+        );
+        // day 2 fails, day 4 is locked, the other days are created anyway
+        assert!(results[0].1.is_ok());
+        assert!(results[1].1.is_err());
+        assert!(results[2]
+            .1
+            .as_ref()
+            .unwrap()
+            .contains(&root.join("day03").join("input.txt")));
+        assert!(matches!(
+            results[3].1.as_ref().unwrap_err().kind(),
+            Kind::Locked(2022, 4)
+        ));
+
+        let _ = remove_dir_all(root);
+    }
+
+    #[test]
+    pub fn test_unified_diff() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+        let new = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk\n";
+        assert_eq!(
+            "--- a/x\n+++ b/x\n\
+             @@ -1,5 +1,5 @@\n a\n-b\n+B\n c\n d\n e\n\
+             @@ -8,3 +8,4 @@\n h\n i\n j\n+k\n",
+            unified_diff(old, new, "x")
+        );
+    }
+
+    #[test]
+    pub fn test_diff_files() {
+        let root = Path::new("target/test_diff_files");
+        let _ = remove_dir_all(root);
+
+        let opts = WriteOptions::new(root);
+        write_files_with(2022, 1, &TestInputProvider {}, &opts).unwrap();
+        let lib_rs = root.join("src").join("lib.rs");
+        let content = fs::read_to_string(&lib_rs).unwrap();
+        fs::write(&lib_rs, content.replace("pub fn", "fn")).unwrap();
+        fs::remove_file(root.join(".gitignore")).unwrap();
+        let main_rs = root.join("src").join("main.rs");
+        let content = fs::read_to_string(&main_rs).unwrap();
+        fs::write(
+            &main_rs,
+            content.replace("\r\n", "\n").replace('\n', "\r\n"),
+        )
+        .unwrap();
+
+        let diffs = diff_files(root, 2022, 1, &opts.with_benches(true));
+        let diff = |file: &str| {
+            diffs
+                .iter()
+                .find(|(path, _)| path.ends_with(file))
+                .map(|(_, diff)| diff.clone())
+                .unwrap()
+        };
+        assert_eq!(FileDiff::Unchanged, diff("README.adoc"));
+        assert!(matches!(diff("Cargo.toml"), FileDiff::Modified(_)));
+        assert_eq!(FileDiff::New, diff(".gitignore"));
+        assert_eq!(FileDiff::New, diff("bench.rs"));
+        assert_eq!(
+            FileDiff::Modified(
+                "--- a/src/main.rs\n+++ b/src/main.rs\nline endings differ\n".to_string()
+            ),
+            diff("main.rs")
+        );
+        match diff("lib.rs") {
+            FileDiff::Modified(diff) => {
+                assert!(diff.starts_with("--- a/src/lib.rs\n+++ b/src/lib.rs\n@@"));
+                assert!(diff.lines().any(|line| line.starts_with("+pub fn")));
+                assert!(diff.lines().any(|line| line.starts_with("-fn")));
+            }
+            diff => panic!("Unexpected diff: {diff:?}"),
+        }
+        assert!(
+            !root.join(".gitignore").exists(),
+            "diff_files must not write"
+        );
+
+        let _ = remove_dir_all(root);
+    }
+
+    #[test]
+    pub fn test_write_files_ensure() {
+        let target_path = Path::new("target/test_write_files_ensure");
+        let _ = remove_dir_all(target_path);
+        write_files_with(
+            2022,
+            25,
+            &TestInputProvider {},
+            &WriteOptions::new(target_path),
+        )
+        .unwrap();
+
+        let main_rs = target_path.join("src").join("main.rs");
+        let lib_rs = target_path.join("src").join("lib.rs");
+        fs::remove_file(&main_rs).unwrap();
+        fs::write(&lib_rs, "my edits").unwrap();
+
+        // failing input provider must not be called
+        let opts = WriteOptions::new(target_path).with_ensure(true);
+        let paths = write_files_with(2022, 25, &FailingInputProvider {}, &opts).unwrap();
+        assert_eq!(vec![main_rs.clone()], paths);
+        assert!(main_rs.is_file());
+        assert_eq!("my edits", fs::read_to_string(&lib_rs).unwrap());
+
+        let _ = remove_dir_all(target_path);
+    }
+
+    #[test]
+    pub fn test_write_files_backup() {
+        let target_path = Path::new("target/test_write_files_backup");
+        let _ = remove_dir_all(target_path);
+        let lib_rs = target_path.join("src").join("lib.rs");
+        let provider = TestInputProvider {};
+
+        let opts = WriteOptions::new(target_path).with_backup(true);
+        write_files_with(2022, 25, &provider, &opts).unwrap();
+        let generated = fs::read_to_string(&lib_rs).unwrap();
+
+        // unchanged files are not backed up
+        write_files_with(2022, 25, &provider, &opts).unwrap();
+        assert!(!target_path.join("src").join("lib.rs.bak").exists());
+
+        // changed files are backed up without replacing previous backups
+        fs::write(&lib_rs, "edit 1").unwrap();
+        write_files_with(2022, 25, &provider, &opts).unwrap();
+        fs::write(&lib_rs, "edit 2").unwrap();
+        write_files_with(2022, 25, &provider, &opts).unwrap();
+
+        assert_eq!(generated, fs::read_to_string(&lib_rs).unwrap());
+        assert_eq!(
+            "edit 1",
+            fs::read_to_string(target_path.join("src").join("lib.rs.bak")).unwrap()
+        );
+        assert_eq!(
+            "edit 2",
+            fs::read_to_string(target_path.join("src").join("lib.rs.bak.1")).unwrap()
+        );
+
+        let _ = remove_dir_all(target_path);
+    }
+
+    #[test]
+    pub fn test_verify_generated() {
+        let target_path = Path::new("target/test_verify_generated");
+        let _ = remove_dir_all(target_path);
+        fs::create_dir_all(target_path.join("src")).unwrap();
+        fs::write(
+            target_path.join("Cargo.toml"),
+            "[package]\nname = \"broken\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+        )
+        .unwrap();
+
+        fs::write(target_path.join("src").join("main.rs"), "fn main() {}\n").unwrap();
+        assert!(verify_generated(target_path).is_ok());
+
+        fs::write(target_path.join("src").join("main.rs"), "fn main() { x }\n").unwrap();
+        let err = verify_generated(target_path).unwrap_err();
+        assert!(err.to_string().contains("cannot find value `x`"), "{err}");
+
+        let _ = remove_dir_all(target_path);
+    }
+
+    #[test]
+    pub fn test_scaffold_util_crate() {
+        let root = Path::new("target/test_scaffold_util_crate");
+        let _ = remove_dir_all(root);
+
+        let paths = scaffold_util_crate(root.join("util")).unwrap();
+        assert_eq!(2, paths.len());
+        fs::write(root.join("util").join("src").join("lib.rs"), "// helpers\n").unwrap();
+        assert!(scaffold_util_crate(root.join("util")).unwrap().is_empty());
+        assert_eq!(
+            "// helpers\n",
+            fs::read_to_string(root.join("util").join("src").join("lib.rs")).unwrap()
+        );
+
+        for (name, opts) in [
+            ("plain", WriteOptions::new(root.join("plain"))),
+            (
+                "util",
+                WriteOptions::new(root.join("util_day"))
+                    .with_benches(true)
+                    .with_util_crate("../util"),
+            ),
+        ] {
+            write_files_with(2022, 1, &TestInputProvider {}, &opts).unwrap();
+            let cargo_toml = fs::read_to_string(opts.path.join("Cargo.toml")).unwrap();
+            let doc = cargo_toml.parse::<DocumentMut>().unwrap();
+            let util = doc["dependencies"].get(UTIL_CRATE_NAME);
+            match name {
+                "plain" => assert!(util.is_none(), "{cargo_toml}"),
+                _ => assert_eq!(
+                    Some("../util"),
+                    util.and_then(|util| util.get("path"))
+                        .and_then(|p| p.as_str()),
+                    "{cargo_toml}"
+                ),
+            }
+        }
+
+        let _ = remove_dir_all(root);
+    }
+
+    #[test]
+    pub fn test_write_files_name_collision() {
+        let root = Path::new("target/test_write_files_name_collision");
+        let _ = remove_dir_all(root);
+        fs::create_dir_all(root.join("other")).unwrap();
+        fs::write(
+            root.join("other").join("Cargo.toml"),
+            "[package]\nname = \"mr-kaffee-2022-25\"\n",
+        )
+        .unwrap();
+
+        let opts = WriteOptions::new(root.join("day25")).with_workspace_root(root);
+        let err = write_files_with(2022, 25, &TestInputProvider {}, &opts).unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            Kind::IoError(err) if err.kind() == std::io::ErrorKind::AlreadyExists
+        ));
+        assert!(err.to_string().contains("other"));
+        assert!(!root.join("day25").exists());
+
+        // the crate in the target directory itself is no collision
+        fs::remove_dir_all(root.join("other")).unwrap();
+        write_files_with(2022, 25, &TestInputProvider {}, &opts).unwrap();
+        let opts = opts.with_force(true);
+        write_files_with(2022, 25, &TestInputProvider {}, &opts).unwrap();
+
+        let _ = remove_dir_all(root);
+    }
+
+    #[test]
+    pub fn test_register_in_workspace() {
+        let workspace_path = Path::new("target/test_register_in_workspace");
+        let _ = remove_dir_all(workspace_path);
+        fs::create_dir_all(workspace_path.join("day01")).unwrap();
+        let manifest_path = workspace_path.join("Cargo.toml");
+        fs::write(
+            &manifest_path,
+            "# my workspace\n[workspace]\nmembers = [\n    \"day00\", # hello world\n]\n",
+        )
+        .unwrap();
+
+        let updated = register_in_workspace(&manifest_path, &workspace_path.join("day01"));
+        assert!(updated.unwrap());
+        let updated = register_in_workspace(&manifest_path, &workspace_path.join("day01"));
+        assert!(!updated.unwrap());
+
+        let manifest = fs::read_to_string(&manifest_path).unwrap();
+        assert!(
+            manifest.contains("# my workspace"),
+            "comment lost: {manifest}"
+        );
+        assert!(
+            manifest.contains("# hello world"),
+            "comment lost: {manifest}"
+        );
+        assert_eq!(1, manifest.matches("\"day01\"").count(), "{manifest}");
+
+        let _ = remove_dir_all(workspace_path);
+    }
+
+    #[test]
+    pub fn test_relative_path() {
+        assert_eq!("b/c", relative_path(Path::new("/a"), Path::new("/a/b/c")));
+        assert_eq!("../d", relative_path(Path::new("/a/b"), Path::new("/a/d")));
+        assert_eq!(".", relative_path(Path::new("/a"), Path::new("/a")));
+    }
+
+    #[test]
+    pub fn test_render_template() {
+        let vars = HashMap::from([
+            ("YEAR".to_string(), "2022".to_string()),
+            ("DAY".to_string(), "7".to_string()),
+        ]);
+
+        // braces in rust code are left untouched
+        let template =
+            "fn main() {\n    let x = {YEAR};\n    println!(\"{}/{DAY}: {x:?}\", x);\n}\n";
+        assert_eq!(
+            "fn main() {\n    let x = 2022;\n    println!(\"{}/7: {x:?}\", x);\n}\n",
+            render_template(template, &vars)
+        );
+
+        // escaped braces
+        assert_eq!(
+            "{YEAR} {2022}",
+            render_template("{{YEAR}} {{{YEAR}}}", &vars)
+        );
+
+        // unknown placeholders are left untouched
+        assert_eq!("{FOO} 2022 {", render_template("{FOO} {YEAR} {", &vars));
+    }
+
     #[test]
     pub fn test_build_var_map() {
         let vars = build_var_map(
@@ -250,5 +4489,27 @@ mod tests {
         );
         assert_eq!("I want to solve 2025/2", vars.get("VAR1").unwrap());
         assert_eq!("2025 25 2025 2 02", vars.get("VAR2").unwrap());
+
+        let vars = build_var_map(
+            || r#"{"AUTHOR": "Jane Doe", "AUTHOR_EMAIL": "jane@example.com"}"#,
+            2025,
+            2,
+        );
+        assert_eq!(
+            "\"Jane Doe <jane@example.com>\"",
+            vars.get("AUTHORS").unwrap()
+        );
+
+        let vars = build_var_map(|| r#"{"AUTHOR": "", "AUTHOR_EMAIL": ""}"#, 2025, 2);
+        assert_eq!("", vars.get("AUTHORS").unwrap());
+        assert_eq!("usize", vars.get("RESULT1_TYPE").unwrap());
+        assert_eq!("usize", vars.get("RESULT2_TYPE").unwrap());
+        assert_eq!("None", vars.get("EXP1").unwrap());
+        assert_eq!("None", vars.get("EXP2").unwrap());
+        assert_eq!("2", vars.get("CRATE_DAY").unwrap());
+
+        let vars = build_var_map(|| r#"{"CRATE_DAY": "{DAY2}"}"#, 2025, 2);
+        assert_eq!("02", vars.get("CRATE_DAY").unwrap());
+        assert_eq!("2", vars.get("DAY").unwrap());
     }
 }
@@ -0,0 +1,83 @@
+//! Module with helpers to test code using [`InputProvider`]s without network access
+use crate::{err::PuzzleError, template::InputProvider};
+use std::collections::HashMap;
+
+/// Input provider returning canned inputs
+///
+/// Inputs and errors are registered per `(year, day)`. For any other day, an input
+/// `"Test input for {year}/{day}\n"` is returned.
+///
+/// # Examples
+/// ```
+/// # use mr_kaffee_aoc::{template::InputProvider, testing::MockInputProvider};
+/// let provider = MockInputProvider::new()
+///     .with_input(2022, 1, "1000\n2000\n")
+///     .with_error(2022, 2, "not yet unlocked");
+/// assert_eq!("1000\n2000\n", provider.load_input(2022, 1).unwrap());
+/// assert_eq!("not yet unlocked", provider.load_input(2022, 2).unwrap_err().to_string());
+/// assert_eq!("Test input for 2022/3\n", provider.load_input(2022, 3).unwrap());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockInputProvider {
+    inputs: HashMap<(u16, u16), Result<String, String>>,
+}
+
+impl MockInputProvider {
+    /// Create a provider without registered inputs
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an `input` to return for `year` and `day`
+    pub fn with_input<S: Into<String>>(mut self, year: u16, day: u16, input: S) -> Self {
+        self.inputs.insert((year, day), Ok(input.into()));
+        self
+    }
+
+    /// Register an error with `message` to return for `year` and `day`
+    pub fn with_error<S: Into<String>>(mut self, year: u16, day: u16, message: S) -> Self {
+        self.inputs.insert((year, day), Err(message.into()));
+        self
+    }
+}
+
+impl InputProvider for MockInputProvider {
+    fn load_input(&self, year: u16, day: u16) -> Result<String, PuzzleError> {
+        match self.inputs.get(&(year, day)) {
+            Some(Ok(input)) => Ok(input.clone()),
+            Some(Err(message)) => Err(PuzzleError::from(message.as_str())),
+            None => Ok(format!("Test input for {}/{}\n", year, day)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::template::{write_files, write_files_with, WriteOptions};
+    use std::{fs, path::Path};
+
+    #[test]
+    pub fn test_mock_input_provider_write_files() {
+        let target_path = Path::new("target/test_mock_input_provider_write_files");
+        let _ = fs::remove_dir_all(target_path);
+        let provider = MockInputProvider::new()
+            .with_input(2022, 1, "canned input")
+            .with_error(2022, 2, "unavailable");
+
+        let result = write_files(target_path, &provider, || "{}", 2022, 1, false);
+        assert!(result.is_ok());
+        assert_eq!(
+            "canned input",
+            fs::read_to_string(target_path.join("input.txt")).unwrap()
+        );
+
+        let opts = WriteOptions::new(target_path)
+            .with_force(true)
+            .with_refresh_input(true);
+        let result = write_files_with(2022, 2, &provider, &opts);
+        assert_eq!("unavailable", result.unwrap_err().to_string());
+
+        let _ = fs::remove_dir_all(target_path);
+    }
+}
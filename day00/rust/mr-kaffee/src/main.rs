@@ -1,24 +1,81 @@
 use clap::Parser;
 use itertools::Itertools;
 use mr_kaffee_aoc::{
+    baseline::{Baseline, DEFAULT_THRESHOLD},
+    color::{paint, Color},
+    config::ConfigFile,
     err::PuzzleError,
-    puzzle_io::PuzzleIO,
-    template::{upd_files, write_files},
+    puzzle_io::{HttpInputProvider, PuzzleIO},
+    registry::PuzzleRegistry,
+    summary::{RunSummary, Status},
+    template::{
+        diff_files, refresh_year_with, register_in_workspace, scaffold_util_crate, upd_files,
+        write_files_from_existing, write_files_range, CachingInputProvider, DependencySpec,
+        FileDiff, InputProvider, NormalizingInputProvider, PostHook, RateLimitedInputProvider,
+        TemplateSet, WriteOptions, DEFAULT_RESULT_TYPE,
+    },
     GenericPuzzle,
 };
-use std::{error::Error, fs, path::PathBuf, time::Instant};
+use std::{
+    error::Error,
+    fs,
+    path::PathBuf,
+    process::{Command, Stdio},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// logger printing messages of level info and above to standard out
+struct StdoutLogger;
+
+impl log::Log for StdoutLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Info
+    }
+
+    fn log(&self, record: &log::Record) {
+        match record.level() {
+            _ if !self.enabled(record.metadata()) => (),
+            log::Level::Info => println!("{}", record.args()),
+            level => println!("{level}: {}", record.args()),
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: StdoutLogger = StdoutLogger;
+
+#[cfg(feature = "peak-alloc")]
+#[global_allocator]
+static ALLOC: mr_kaffee_aoc::alloc::PeakAlloc = mr_kaffee_aoc::alloc::PeakAlloc;
 
 fn main() -> Result<(), Box<dyn Error>> {
     // parse command line
     let cli = cli::Cli::parse();
+    if cli.quiet {
+        mr_kaffee_aoc::set_quiet(true);
+    }
+
+    log::set_logger(&LOGGER).map_err(|err| err.to_string())?;
+    log::set_max_level(if mr_kaffee_aoc::is_quiet() {
+        log::LevelFilter::Warn
+    } else {
+        log::LevelFilter::Info
+    });
     match cli.command {
-        Some(cli::Commands::Run(run)) => exec_run(run),
-        Some(cli::Commands::Init(init)) => exec_init(init)?,
+        Some(cli::Commands::Run(run)) => exec_run(run)?,
+        Some(cli::Commands::Init(init)) => exec_init(*init)?,
         Some(cli::Commands::Submit(submit)) => exec_submit(submit)?,
+        Some(cli::Commands::Year(year)) => exec_year(year),
+        Some(cli::Commands::Refresh(refresh)) => exec_refresh(refresh)?,
         None => exec_run(cli::Run {
             years: cli::Filter::Range(2015..=2022),
             days: cli::Filter::Range(0..=25),
-        }),
+            baseline: None,
+            threshold: DEFAULT_THRESHOLD,
+            update_baseline: false,
+            results: None,
+        })?,
     };
 
     Ok(())
@@ -47,9 +104,59 @@ fn puzzles() -> Vec<Box<dyn GenericPuzzle>> {
     puzzles
 }
 
-fn exec_run(run: cli::Run) {
+fn exec_run(run: cli::Run) -> Result<(), PuzzleError> {
     // get and sort puzzles
     let puzzles = puzzles();
+    let quiet = mr_kaffee_aoc::is_quiet();
+
+    if run.baseline.is_some() || run.results.is_some() {
+        let mut summary = RunSummary::default();
+        puzzles
+            .iter()
+            .filter(|puzzle| run.years.accept(puzzle.year()) && run.days.accept(puzzle.day()))
+            .for_each(|puzzle| puzzle.solve_summary(&mut summary));
+        if quiet {
+            // answers only, one per line, errors are still printed
+            for row in &summary.rows {
+                if row.status == Status::Error {
+                    let line = format!(
+                        "Error solving puzzle {}/{}: {}",
+                        row.year, row.day, row.result
+                    );
+                    println!("{}", paint(line, Color::Red));
+                } else {
+                    println!("{}", row.result);
+                }
+            }
+        } else {
+            println!("{summary}");
+        }
+
+        if let Some(path) = &run.results {
+            summary.append_csv(path)?;
+            if !quiet {
+                println!("\n====> Answers appended to {}", path.to_string_lossy());
+            }
+        }
+
+        if let Some(path) = &run.baseline {
+            let baseline = Baseline::new(path)
+                .with_threshold(run.threshold)
+                .with_update(run.update_baseline);
+            let regressions = baseline.check_summary(&summary)?;
+            if !quiet {
+                for regression in &regressions {
+                    println!("{regression}");
+                }
+                println!(
+                    "\n====> {} stars slower than the baseline {}",
+                    regressions.len(),
+                    path.to_string_lossy()
+                );
+            }
+        }
+        return Ok(());
+    }
 
     // run puzzles grouped by year
     let timer = Instant::now();
@@ -59,18 +166,28 @@ fn exec_run(run: cli::Run) {
         .group_by(|puzzle| puzzle.year())
         .into_iter()
         .fold((0, 0), |(cnt, oks), (year, puzzles)| {
-            println!();
+            if !quiet {
+                println!();
+            }
             let timer = Instant::now();
             let (sub_cnt, sub_oks) = puzzles.into_iter().fold((0, 0), |(cnt, oks), puzzle| {
-                println!();
+                if !quiet {
+                    println!();
+                }
                 (cnt + 1, oks + puzzle.solve_handle_err() as usize)
             });
             let d = timer.elapsed();
-            println!("\n==> Solved {sub_oks} out of {sub_cnt} puzzles for {year} in {d:?}");
+            if !quiet {
+                println!("\n==> Solved {sub_oks} out of {sub_cnt} puzzles for {year} in {d:?}");
+            }
             (cnt + sub_cnt, oks + sub_oks)
         });
     let d = timer.elapsed();
-    println!("\n====> Solved {oks} out of {cnt} puzzles in {d:?}");
+    if !quiet {
+        println!("\n====> Solved {oks} out of {cnt} puzzles in {d:?}");
+    }
+
+    Ok(())
 }
 
 fn read_config() -> String {
@@ -91,26 +208,390 @@ fn read_config() -> String {
     }
 }
 
+/// get puzzle IO with the session from the environment, the configuration file, or the file
+/// `session.cookie`, in that order
+fn puzzle_io(config_file: &ConfigFile) -> Result<PuzzleIO, PuzzleError> {
+    if let Ok(puzzle_io) = PuzzleIO::from_env() {
+        return Ok(puzzle_io);
+    }
+
+    if let Some(puzzle_io) = config_file.puzzle_io() {
+        return Ok(puzzle_io);
+    }
+
+    let path = PathBuf::from("session.cookie");
+    Ok(PuzzleIO::try_from(path.as_path())?)
+}
+
+/// get the current date as `(year, month, day)` in the AoC time zone (UTC-5)
+fn aoc_today() -> (u16, u16, u16) {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    civil_from_days((secs.saturating_sub(5 * 3600) / 86_400) as i64)
+}
+
+/// convert days since 1970-01-01 to `(year, month, day)`,
+/// see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (u16, u16, u16) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    (y as u16, m as u16, d as u16)
+}
+
+/// resolve year and day, default to the current year and, within December, the current day
+fn resolve_date(year: Option<u16>, day: Option<u16>) -> Result<(u16, u16), PuzzleError> {
+    let (today_year, today_month, today_day) = aoc_today();
+    let year = year.unwrap_or(today_year);
+    let day = match day {
+        Some(day) => day,
+        None if today_month == 12 && today_day <= 25 => today_day,
+        None => return Err("No day given and today is not an advent day".into()),
+    };
+    Ok((year, day))
+}
+
+fn exec_year(year: cli::Year) {
+    let registry = puzzles().into_iter().collect::<PuzzleRegistry>();
+    let failures = registry.run_all(year.year);
+    if failures > 0 {
+        std::process::exit(failures.min(255) as i32);
+    }
+}
+
+fn exec_refresh(refresh: cli::Refresh) -> Result<(), PuzzleError> {
+    let config_file = ConfigFile::load()?;
+    let cache_dir = match refresh.cache_dir {
+        Some(cache_dir) => cache_dir,
+        None => config_file.resolve_cache_dir()?,
+    };
+    let puzzle_io = puzzle_io(&config_file)?;
+    let delay = Duration::from_millis(refresh.delay_ms);
+    let rate_limited_provider =
+        RateLimitedInputProvider::new(&puzzle_io, &cache_dir).with_min_interval(delay);
+    let provider = CachingInputProvider::new(&rate_limited_provider, &cache_dir)
+        .with_force_refresh(refresh.force);
+    let failed = refresh_year_with(&provider, refresh.year, delay)
+        .into_iter()
+        .filter(|(_, result)| result.is_err())
+        .count();
+    println!(
+        "{failed} days without input in {}",
+        cache_dir.to_string_lossy()
+    );
+    Ok(())
+}
+
 fn exec_init(init: cli::Init) -> Result<(), PuzzleError> {
-    let input_provider = PuzzleIO::try_from(PathBuf::from("session.cookie").as_path())?;
+    if init.list {
+        let mut opts = WriteOptions::new(&init.target_path).with_benches(init.with_benches);
+        if init.example_test.is_some() {
+            opts = opts.with_example_test("", "");
+        }
+        for file in opts.manifest() {
+            println!("{}", init.target_path.join(file).to_string_lossy());
+        }
+        return Ok(());
+    }
+
+    let config_file = ConfigFile::load()?;
+    let year = init.year.or(config_file.default_year);
+    let (year, days) = match (init.since, init.until) {
+        (None, None) => {
+            let (year, day) = resolve_date(year, init.day)?;
+            (year, day..=day)
+        }
+        (since, until) => {
+            let (today_year, today_month, today_day) = aoc_today();
+            let year = year.unwrap_or(today_year);
+            let today =
+                (year == today_year && today_month == 12 && today_day <= 25).then_some(today_day);
+            let (since, until) = (since.unwrap_or(1), until.or(today).unwrap_or(25));
+            if since > until {
+                return Err(
+                    format!("Empty day range: --since {since} is after --until {until}").into(),
+                );
+            }
+            (year, since..=until)
+        }
+    };
+    let input_provider: Box<dyn InputProvider> = match (&init.input_source, &init.input_url) {
+        (Some(uri), _) => <dyn InputProvider>::from_uri(uri)?,
+        (None, Some(url)) => Box::new(http_input_provider(url, &init.input_header)?),
+        (None, None) => {
+            let puzzle_io = match &init.session {
+                Some(session) => PuzzleIO::from(session.as_str()),
+                None => puzzle_io(&config_file)?,
+            };
+            Box::new(match &init.proxy {
+                Some(proxy) => puzzle_io.with_proxy(proxy)?,
+                None => puzzle_io,
+            })
+        }
+    };
+    let normalizing_provider = NormalizingInputProvider::new(input_provider.as_ref());
+    let input_provider: &dyn InputProvider = if init.normalize_input {
+        &normalizing_provider
+    } else {
+        input_provider.as_ref()
+    };
+    // requests to the server are spaced out across invocations, local sources are not limited
+    let is_local = init.input_source.as_deref().is_some_and(|uri| {
+        ["file://", "cache://", "stdin://"]
+            .iter()
+            .any(|scheme| uri.starts_with(scheme))
+    });
+    let stamp_dir = match (&init.cache_dir, &config_file.cache_dir) {
+        (Some(cache_dir), _) | (None, Some(cache_dir)) => Some(cache_dir.clone()),
+        (None, None) => ConfigFile::default_cache_dir().ok(),
+    };
+    let rate_limited_provider = stamp_dir.filter(|_| !is_local).map(|stamp_dir| {
+        RateLimitedInputProvider::new(input_provider, stamp_dir)
+            .with_min_interval(Duration::from_millis(init.min_interval_ms))
+    });
+    let input_provider: &dyn InputProvider = match &rate_limited_provider {
+        Some(rate_limited_provider) => rate_limited_provider,
+        None => input_provider,
+    };
+    let caching_provider = init
+        .cache_dir
+        .as_ref()
+        .or(config_file.cache_dir.as_ref())
+        .map(|cache_dir| CachingInputProvider::new(input_provider, cache_dir));
+    let input_provider: &dyn InputProvider = match &caching_provider {
+        Some(caching_provider) => caching_provider,
+        None => input_provider,
+    };
     let config = read_config();
+    let templates = match init
+        .template_dir
+        .as_ref()
+        .or(config_file.template_dir.as_ref())
+    {
+        Some(template_dir) => TemplateSet::from_dir(template_dir)?,
+        None => TemplateSet::default(),
+    };
+
+    let mut opts = WriteOptions::new(&init.target_path)
+        .with_template_set(templates)
+        .with_config(config.as_str())
+        .with_force(init.force)
+        .with_dry_run(init.dry_run)
+        .with_refresh_input(init.refresh_input)
+        .with_refresh_prompt(init.refresh_prompt)
+        .with_benches(init.with_benches)
+        .with_verify(init.verify)
+        .with_backup(init.backup)
+        .with_ensure(init.ensure)
+        .with_commit_inputs(init.commit_inputs)
+        .with_serde(init.serde)
+        .with_strict_input(init.strict_input)
+        .with_format(init.format);
+    match (&init.aoc_version, &init.aoc_git) {
+        (Some(version), _) => opts = opts.with_dependency(DependencySpec::Version(version.clone())),
+        (None, Some(url)) => {
+            opts = opts.with_dependency(DependencySpec::Git {
+                url: url.clone(),
+                rev: init.aoc_rev.clone(),
+            })
+        }
+        (None, None) => (),
+    }
+    if init.result1_type.is_some() || init.result2_type.is_some() {
+        let default = || DEFAULT_RESULT_TYPE.to_string();
+        opts = opts.with_result_types(
+            init.result1_type.clone().unwrap_or_else(default),
+            init.result2_type.clone().unwrap_or_else(default),
+        );
+    }
+    if let Some(exp1) = &init.exp1 {
+        opts = opts.with_exp1(exp1.as_str());
+    }
+    if let Some(exp2) = &init.exp2 {
+        opts = opts.with_exp2(exp2.as_str());
+    }
+    if let Some(edition) = &init.edition {
+        opts = opts.with_edition(edition.as_str());
+    }
+    if let Some(rust_version) = &init.rust_version {
+        opts = opts.with_rust_version(rust_version.as_str());
+    }
+    if let Some([exp1, exp2]) = init.example_test.as_deref() {
+        opts = opts.with_example_test(exp1.as_str(), exp2.as_str());
+    }
+    if let Some(workspace) = &init.workspace {
+        // the workspace root is also checked for packages with the same name
+        match workspace.parent() {
+            Some(root) if !root.as_os_str().is_empty() => opts = opts.with_workspace_root(root),
+            _ => opts = opts.with_workspace_root("."),
+        }
+    }
+
+    if let Some(util_crate) = &init.util_crate {
+        opts = opts.with_util_crate(util_crate);
+        let util_path = opts.for_day(year, *days.start()).path.join(util_crate);
+        if !init.dry_run && !init.diff && !util_path.join("Cargo.toml").exists() {
+            for path in scaffold_util_crate(&util_path)? {
+                println!("Created {}", path.to_string_lossy());
+            }
+        }
+    }
+
+    if let Some(post_hook) = &init.post_hook {
+        opts = opts.with_post_hook(PostHook::shell(post_hook));
+    }
+
+    if init.diff {
+        for day in days {
+            for (path, diff) in diff_files(&opts.for_day(year, day).path, year, day, &opts) {
+                match diff {
+                    FileDiff::Unchanged => println!("Unchanged {}", path.to_string_lossy()),
+                    FileDiff::New => println!("New       {}", path.to_string_lossy()),
+                    FileDiff::Modified(diff) => {
+                        println!("Modified  {}", path.to_string_lossy());
+                        print!("{diff}");
+                    }
+                }
+            }
+        }
+        return Ok(());
+    }
 
-    write_files(
-        &init.target_path,
-        &input_provider,
-        || &config,
-        init.year,
-        init.day,
-        init.force,
-    )?;
+    // the session is not needed if all inputs to write are served from the cache
+    let needs_input = days.clone().any(|day| {
+        (init.refresh_input || !opts.for_day(year, day).path.join("input.txt").exists())
+            && !caching_provider
+                .as_ref()
+                .is_some_and(|caching_provider| caching_provider.is_cached(year, day))
+    });
+    if needs_input && !init.dry_run {
+        match input_provider.check_session() {
+            Ok(true) => (),
+            Ok(false) => {
+                return Err(PuzzleError::from(
+                    "The session cookie is invalid or expired, log in to adventofcode.com and \
+                    update it with --session, AOC_SESSION, or the configuration file",
+                ))
+            }
+            Err(err) => log::warn!("Could not check the session: {err}"),
+        }
+    }
 
-    if let Some(runner_path) = init.runner_path {
-        upd_files(runner_path.as_path(), || &config, init.year, init.day)?;
+    let results = match &init.from {
+        Some(source_path) => days
+            .map(|day| {
+                let opts = opts.for_day(year, day);
+                let result =
+                    write_files_from_existing(source_path, year, day, input_provider, &opts);
+                (day, result)
+            })
+            .collect::<Vec<_>>(),
+        None => write_files_range(input_provider, year, days, &opts),
+    };
+    if let [(_, Err(_))] = results.as_slice() {
+        // a single day fails as a whole
+        return results.into_iter().next().unwrap().1.map(|_| ());
+    }
+
+    let mut failed = 0;
+    for (day, result) in results {
+        match result {
+            Ok(paths) => finish_init(&init, &opts.for_day(year, day), &config, year, day, paths)?,
+            Err(err) => {
+                println!("Could not create files for {year}/{day}: {err}");
+                failed += 1;
+            }
+        }
+    }
+    if failed > 0 {
+        println!("{failed} days failed");
     }
 
     Ok(())
 }
 
+fn finish_init(
+    init: &cli::Init,
+    opts: &WriteOptions,
+    config: &str,
+    year: u16,
+    day: u16,
+    paths: Vec<PathBuf>,
+) -> Result<(), PuzzleError> {
+    if init.dry_run {
+        println!("Would create {} files for {year}/{day}", paths.len());
+        return Ok(());
+    }
+
+    println!("Created files for {year}/{day}:");
+    for path in paths {
+        println!("  {}", path.to_string_lossy());
+    }
+
+    if let Some(workspace) = &init.workspace {
+        register_in_workspace(workspace.as_path(), &opts.path)?;
+    }
+
+    if let Some(runner_path) = &init.runner_path {
+        upd_files(runner_path.as_path(), || config, year, day)?;
+    }
+
+    if init.open {
+        let url = format!("{}/{year}/day/{day}", PuzzleIO::DEFAULT_BASE_URL);
+        if let Err(err) = open_in_browser(&url) {
+            println!("Could not open browser ({err}), read the puzzle at {url}");
+        }
+    }
+
+    Ok(())
+}
+
+/// open `url` in the default browser with the platform's launcher
+fn open_in_browser(url: &str) -> std::io::Result<()> {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else {
+        Command::new("xdg-open")
+    };
+    let status = command
+        .arg(url)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::other(format!(
+            "launcher exited with {status}"
+        )))
+    }
+}
+
+/// create an input provider for the URL template `url` with headers given as `Name: value`
+fn http_input_provider(url: &str, headers: &[String]) -> Result<HttpInputProvider, PuzzleError> {
+    headers
+        .iter()
+        .try_fold(
+            HttpInputProvider::new(url),
+            |provider, header| match header.split_once(':') {
+                Some((name, value)) => Ok(provider.with_header(name.trim(), value.trim())),
+                None => Err(format!("Invalid header '{header}', expected 'Name: value'").into()),
+            },
+        )
+}
+
 fn exec_submit(submit: cli::Submit) -> Result<(), Box<dyn Error>> {
     let puzzles = puzzles();
     let puzzle = puzzles
@@ -123,7 +604,7 @@ fn exec_submit(submit: cli::Submit) -> Result<(), Box<dyn Error>> {
             v => return Err(format!("Illegal part: {v}").into()),
         };
         if let Some(result) = result {
-            let puzzle_io = PuzzleIO::try_from(PathBuf::from("session.cookie").as_path())?;
+            let puzzle_io = puzzle_io(&ConfigFile::load()?)?;
             puzzle_io.submit_result(submit.year, submit.day, level, &result)?;
         } else {
             println!(
@@ -141,6 +622,10 @@ fn exec_submit(submit: cli::Submit) -> Result<(), Box<dyn Error>> {
 mod cli {
     use clap::{Args, Parser, Subcommand};
     use lazy_static::lazy_static;
+    use mr_kaffee_aoc::{
+        baseline::{DEFAULT_BASELINE_PATH, DEFAULT_THRESHOLD},
+        summary::DEFAULT_RESULTS_PATH,
+    };
     use regex::Regex;
     use std::{ops::RangeInclusive, path::PathBuf, str::FromStr};
 
@@ -149,6 +634,11 @@ mod cli {
     pub(crate) struct Cli {
         #[command(subcommand)]
         pub(crate) command: Option<Commands>,
+
+        /// print only the results of solved stars and warnings, also enabled by the
+        /// `AOC_QUIET` environment variable
+        #[arg(long, short, global = true)]
+        pub(crate) quiet: bool,
     }
 
     #[derive(Subcommand, Debug)]
@@ -157,10 +647,39 @@ mod cli {
         Run(Run),
 
         /// initializes a new puzzle from a template
-        Init(Init),
+        Init(Box<Init>),
 
         /// submits puzzle solution
         Submit(Submit),
+
+        /// runs all puzzles of a year and prints a summary
+        Year(Year),
+
+        /// fetches the inputs of all unlocked days of a year into the cache directory
+        Refresh(Refresh),
+    }
+
+    #[derive(Args, Debug)]
+    pub(crate) struct Year {
+        pub(crate) year: u16,
+    }
+
+    #[derive(Args, Debug)]
+    pub(crate) struct Refresh {
+        pub(crate) year: u16,
+
+        /// minimum delay between two requests in milliseconds
+        #[arg(long, default_value_t = 500)]
+        pub(crate) delay_ms: u64,
+
+        /// download cached inputs again, the cache is only updated if an input changed
+        #[arg(long)]
+        pub(crate) force: bool,
+
+        /// directory to cache inputs in, defaults to `cache_dir` from the configuration file or
+        /// the platform's cache directory, e.g., `~/.cache/aoc`
+        #[arg(long)]
+        pub(crate) cache_dir: Option<PathBuf>,
     }
 
     #[derive(Args, Debug)]
@@ -170,24 +689,212 @@ mod cli {
 
         #[arg(long, short, value_parser = parse_filter_non_empty, default_value_t = Filter::Range(0..=25))]
         pub(crate) days: Filter,
+
+        /// print a summary table and compare the time of every star against the baseline in this
+        /// JSON file, stars missing in the baseline are recorded
+        #[arg(long, num_args = 0..=1, default_missing_value = DEFAULT_BASELINE_PATH)]
+        pub(crate) baseline: Option<PathBuf>,
+
+        /// percentage a star may get slower than its baseline before it is flagged
+        #[arg(long, requires = "baseline", default_value_t = DEFAULT_THRESHOLD)]
+        pub(crate) threshold: f64,
+
+        /// replace the recorded durations in the baseline with the durations of this run
+        #[arg(long, requires = "baseline")]
+        pub(crate) update_baseline: bool,
+
+        /// print a summary table and append the answers with a timestamp to this CSV file
+        #[arg(long, num_args = 0..=1, default_missing_value = DEFAULT_RESULTS_PATH)]
+        pub(crate) results: Option<PathBuf>,
     }
 
     #[derive(Args, Debug)]
     pub(crate) struct Init {
-        #[arg(short, long)]
+        #[arg(short, long, visible_alias = "target")]
         pub(crate) target_path: PathBuf,
 
-        #[arg(short, long, value_parser = clap::value_parser!(u16).range(2015..=2022))]
-        pub(crate) year: u16,
+        /// defaults to `default_year` from the configuration file or the current year
+        #[arg(short, long, value_parser = clap::value_parser!(u16).range(2015..))]
+        pub(crate) year: Option<u16>,
 
+        /// defaults to the current day in December
         #[arg(short, long, value_parser = clap::value_parser!(u16).range(1..=25))]
-        pub(crate) day: u16,
+        pub(crate) day: Option<u16>,
+
+        /// first day of a range of days to create, the target path is rendered for every day,
+        /// e.g., `day{DAY2}/rust/mr-kaffee`; defaults to 1 if `--until` is given
+        #[arg(long, conflicts_with = "day", value_parser = clap::value_parser!(u16).range(1..=25))]
+        pub(crate) since: Option<u16>,
+
+        /// last day of a range of days to create, defaults to the current day in December or 25
+        #[arg(long, conflicts_with = "day", value_parser = clap::value_parser!(u16).range(1..=25))]
+        pub(crate) until: Option<u16>,
+
+        /// session cookie, defaults to the `AOC_SESSION` environment variable or the file
+        /// `session.cookie`
+        #[arg(long)]
+        pub(crate) session: Option<String>,
 
         #[arg(short, long)]
         pub(crate) force: bool,
 
         #[arg(short, long)]
         pub(crate) runner_path: Option<PathBuf>,
+
+        /// download and replace the input even if it already exists
+        #[arg(long)]
+        pub(crate) refresh_input: bool,
+
+        /// with --ensure, replace an existing README.md whose part 2 was still locked
+        #[arg(long)]
+        pub(crate) refresh_prompt: bool,
+
+        /// print the files that would be written without writing anything
+        #[arg(long)]
+        pub(crate) dry_run: bool,
+
+        /// list the files generated from templates and exit, README.md is only written if a
+        /// puzzle description is available
+        #[arg(long)]
+        pub(crate) list: bool,
+
+        /// show how the files generated from templates differ from the existing files and exit
+        /// without writing anything
+        #[arg(long)]
+        pub(crate) diff: bool,
+
+        /// proxy URL used to download the input
+        #[arg(long, conflicts_with = "input_url")]
+        pub(crate) proxy: Option<String>,
+
+        /// download the input from this URL instead of adventofcode.com, e.g., a private mirror;
+        /// `{year}` and `{day}` are replaced
+        #[arg(long, conflicts_with = "session")]
+        pub(crate) input_url: Option<String>,
+
+        /// where to load the input from: `file://{dir}`, `cache://{dir}`,
+        /// `https://{session}@adventofcode.com`, or `stdin://` to pipe or paste it
+        #[arg(long, conflicts_with_all = ["session", "proxy", "input_url"])]
+        pub(crate) input_source: Option<String>,
+
+        /// header sent with requests to the `--input-url` as `Name: value`, may be repeated
+        #[arg(long, requires = "input_url")]
+        pub(crate) input_header: Vec<String>,
+
+        /// convert CRLF to LF and strip one trailing newline from the input
+        #[arg(long)]
+        pub(crate) normalize_input: bool,
+
+        /// fail if the downloaded input is suspiciously short instead of printing a warning
+        #[arg(long)]
+        pub(crate) strict_input: bool,
+
+        /// result type of star 1, defaults to usize
+        #[arg(long)]
+        pub(crate) result1_type: Option<String>,
+
+        /// result type of star 2, defaults to usize
+        #[arg(long)]
+        pub(crate) result2_type: Option<String>,
+
+        /// only create files which do not exist, leave existing files untouched
+        #[arg(long)]
+        pub(crate) ensure: bool,
+
+        /// do not add input.txt and example.txt to the generated .gitignore
+        #[arg(long)]
+        pub(crate) commit_inputs: bool,
+
+        /// derive Serialize for PuzzleData behind a feature serde of the new crate
+        #[arg(long)]
+        pub(crate) serde: bool,
+
+        /// overwrite an existing target, keeping backups of changed files
+        #[arg(long)]
+        pub(crate) backup: bool,
+
+        /// check that the generated crate compiles
+        #[arg(long)]
+        pub(crate) verify: bool,
+
+        /// format the generated Rust files with rustfmt
+        #[arg(long)]
+        pub(crate) format: bool,
+
+        /// depend on a published version of mr-kaffee-aoc instead of a path
+        #[arg(long, conflicts_with = "aoc_git")]
+        pub(crate) aoc_version: Option<String>,
+
+        /// depend on mr-kaffee-aoc from a git repository instead of a path
+        #[arg(long)]
+        pub(crate) aoc_git: Option<String>,
+
+        /// revision of the git repository given with --aoc-git
+        #[arg(long, requires = "aoc_git")]
+        pub(crate) aoc_rev: Option<String>,
+
+        /// expected result of star 1
+        #[arg(long)]
+        pub(crate) exp1: Option<String>,
+
+        /// expected result of star 2
+        #[arg(long)]
+        pub(crate) exp2: Option<String>,
+
+        /// create a criterion benchmark for both stars
+        #[arg(long)]
+        pub(crate) with_benches: bool,
+
+        /// Rust edition of the new crate, defaults to 2021
+        #[arg(long)]
+        pub(crate) edition: Option<String>,
+
+        /// minimum supported Rust version of the new crate, e.g., 1.70
+        #[arg(long)]
+        pub(crate) rust_version: Option<String>,
+
+        /// start from src/lib.rs and src/main.rs of the existing day crate at this path instead
+        /// of the templates, expected results are reset
+        #[arg(long, conflicts_with = "diff")]
+        pub(crate) from: Option<PathBuf>,
+
+        /// create an integration test tests/example.rs checking the results of both stars for
+        /// example.txt
+        #[arg(long, num_args = 2, value_names = ["EXP1", "EXP2"])]
+        pub(crate) example_test: Option<Vec<String>>,
+
+        /// directory with templates overriding the built-in ones, defaults to `template_dir`
+        /// from the configuration file
+        #[arg(long)]
+        pub(crate) template_dir: Option<PathBuf>,
+
+        /// workspace manifest to register the new crate in
+        #[arg(long)]
+        pub(crate) workspace: Option<PathBuf>,
+
+        /// cache downloaded inputs in this directory, overrides `cache_dir` from the
+        /// configuration file
+        #[arg(long)]
+        pub(crate) cache_dir: Option<PathBuf>,
+
+        /// minimum interval between two requests to the server in milliseconds, kept across
+        /// invocations with a timestamp in the cache directory
+        #[arg(long, default_value_t = 1000)]
+        pub(crate) min_interval_ms: u64,
+
+        /// open the puzzle page in the default browser after creating the files
+        #[arg(long)]
+        pub(crate) open: bool,
+
+        /// depend on the shared utility crate mr-kaffee-util at this path relative to the
+        /// target path, a skeleton is created if it does not exist
+        #[arg(long)]
+        pub(crate) util_crate: Option<PathBuf>,
+
+        /// shell command run in the new directory after the files were created, with the
+        /// environment variables AOC_YEAR and AOC_DAY set
+        #[arg(long)]
+        pub(crate) post_hook: Option<String>,
     }
 
     #[derive(Args, Debug)]
@@ -315,3 +1022,10 @@ mod cli {
         assert_eq!(s.parse::<Filter>().unwrap(), filter);
     }
 }
+
+#[test]
+fn test_civil_from_days() {
+    assert_eq!((1970, 1, 1), civil_from_days(0));
+    assert_eq!((2022, 12, 1), civil_from_days(19_327));
+    assert_eq!((2024, 2, 29), civil_from_days(19_782));
+}